@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use utils::{Error, Solution, policy};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectionType {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Direction {
+    pub direction: DirectionType,
+    pub steps: u32,
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Err("empty input".to_string());
+        }
+
+        let (dir_part, steps_str) = trimmed.split_at(1);
+        let direction = match dir_part.chars().next() {
+            Some('L') => DirectionType::Left,
+            Some('R') => DirectionType::Right,
+            Some(c) => return Err(format!("invalid direction: '{}'", c)),
+            None => return Err("missing direction".to_string()),
+        };
+
+        let steps = steps_str
+            .parse::<u32>()
+            .map_err(|_| format!("invalid steps: '{}'", steps_str))?;
+
+        Ok(Direction { direction, steps })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Rotation {
+    /// Current position on the 0..99 dial
+    pub current: u32,
+    /// Total number of times we've pointed at 0 while moving
+    pub zero_hits: u64,
+    /// Number of times we've landed exactly on 0
+    pub exact_landings: u64,
+}
+
+impl Rotation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn starting_at(rotation: u32) -> Self {
+        Self {
+            current: rotation % 100,
+            ..Self::default()
+        }
+    }
+
+    fn count_zero_hits(&self, steps: u32, distance_to_zero: u32) -> u64 {
+        if steps == 0 || distance_to_zero > steps {
+            return 0;
+        }
+
+        // First hit, then once per full 100 steps after that
+        1 + ((steps - distance_to_zero) / 100) as u64
+    }
+
+    fn count_zero_hits_right(&self, steps: u32) -> u64 {
+        let p = self.current; // 0..99
+        // First time we hit 0 going right is after (100 - p) steps (or 100 if p == 0)
+        let distance_to_zero = if p == 0 { 100 } else { 100 - p };
+        self.count_zero_hits(steps, distance_to_zero)
+    }
+
+    fn count_zero_hits_left(&self, steps: u32) -> u64 {
+        let p = self.current; // 0..99
+        // First time we hit 0 going left is after p steps (or 100 if p == 0)
+        let distance_to_zero = if p == 0 { 100 } else { p };
+        self.count_zero_hits(steps, distance_to_zero)
+    }
+
+    pub fn rotate_right(&mut self, steps: u32) {
+        self.zero_hits += self.count_zero_hits_right(steps);
+        self.current = (self.current + steps) % 100;
+        if self.current == 0 {
+            self.exact_landings += 1;
+        }
+    }
+
+    pub fn rotate_left(&mut self, steps: u32) {
+        self.zero_hits += self.count_zero_hits_left(steps);
+        let steps_mod = steps % 100;
+        self.current = (self.current + 100 - steps_mod) % 100;
+        if self.current == 0 {
+            self.exact_landings += 1;
+        }
+    }
+
+    pub fn rotate(&mut self, direction: &Direction) {
+        match direction.direction {
+            DirectionType::Left => self.rotate_left(direction.steps),
+            DirectionType::Right => self.rotate_right(direction.steps),
+        }
+    }
+}
+
+pub fn parse_directions(input: &str) -> Result<Vec<Direction>, Error> {
+    let mut directions = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let direction = line
+            .parse::<Direction>()
+            .map_err(|e| Error::new(e).on_line(idx + 1, line));
+        directions.extend(policy::line(direction)?);
+    }
+
+    Ok(directions)
+}
+
+fn rotate_all(directions: &[Direction]) -> Rotation {
+    let mut rotation = Rotation::starting_at(50);
+
+    for direction in directions {
+        rotation.rotate(direction);
+    }
+
+    rotation
+}
+
+/// Number of times the dial lands exactly on zero
+pub fn solve_part1(directions: &[Direction]) -> u64 {
+    rotate_all(directions).exact_landings
+}
+
+/// Number of times the dial points at zero, including mid-rotation
+pub fn solve_part2(directions: &[Direction]) -> u64 {
+    rotate_all(directions).zero_hits
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<Direction>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Direction>, Error> {
+        parse_directions(input)
+    }
+
+    fn part1(directions: &Vec<Direction>) -> u64 {
+        solve_part1(directions)
+    }
+
+    fn part2(directions: &Vec<Direction>) -> u64 {
+        solve_part2(directions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82"#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_directions(TEST_INPUT).unwrap());
+        assert_eq!(result, 3);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_directions(TEST_INPUT).unwrap());
+        assert_eq!(result, 6);
+    }
+
+    #[test]
+    fn test_invalid_line() {
+        let err = parse_directions("L68\nX30\nR48").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: invalid direction: 'X' (found 'X30')"
+        );
+    }
+
+    #[test]
+    fn test_lenient() {
+        policy::set(policy::Policy::Lenient);
+        let directions = parse_directions("L68\nX30\nR48").unwrap();
+        policy::set(policy::Policy::Strict);
+
+        assert_eq!(directions.len(), 2);
+        let skipped = policy::take_skipped();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].location.as_ref().unwrap().line, 2);
+    }
+}
@@ -0,0 +1,521 @@
+use utils::parse::{self, Line};
+use utils::{Error, Rational, Solution, policy, rref, search};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indicator {
+    Off,
+    On,
+}
+
+type Indicators = Vec<Indicator>;
+
+impl Indicator {
+    pub fn toggle(self) -> Self {
+        match self {
+            Indicator::Off => Indicator::On,
+            Indicator::On => Indicator::Off,
+        }
+    }
+}
+
+type Sequence = Vec<usize>;
+
+#[derive(Debug, Clone)]
+pub struct Machine {
+    indicators: Vec<Indicator>,
+    buttons: Vec<Sequence>,
+    ignition: Vec<Indicator>,
+}
+
+impl Machine {
+    /// Fewest button presses that take the indicators from all off to the
+    /// ignition pattern
+    pub fn min_moves_to_ignition(&self) -> i64 {
+        let press = |state: &Indicators| {
+            self.buttons
+                .iter()
+                .map(|sequence| {
+                    let mut next = state.clone();
+                    for &idx in sequence {
+                        if let Some(indicator) = next.get_mut(idx) {
+                            *indicator = indicator.toggle();
+                        }
+                    }
+                    next
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let path = search::bfs_to([self.indicators.clone()], press, |state| {
+            *state == self.ignition
+        })
+        .expect("parse_machine checks the ignition pattern can be reached");
+        path.cost as i64
+    }
+}
+
+/// Whether pressing some of the buttons, each at most once, turns on exactly
+/// the lights that are on in `ignition`. Pressing a button twice undoes it,
+/// so nothing else can be reached from all off.
+fn can_reach(ignition: &[Indicator], buttons: &[Sequence]) -> bool {
+    // Gaussian elimination over GF(2), one row per light: which buttons
+    // toggle it, then whether it has to end up on
+    let mut rows: Vec<Vec<bool>> = ignition
+        .iter()
+        .enumerate()
+        .map(|(light, &state)| {
+            let mut row: Vec<bool> = buttons
+                .iter()
+                .map(|button| button.iter().filter(|&&idx| idx == light).count() % 2 == 1)
+                .collect();
+            row.push(state == Indicator::On);
+            row
+        })
+        .collect();
+
+    let mut rank = 0;
+    for col in 0..buttons.len() {
+        let Some(pivot) = (rank..rows.len()).find(|&row| rows[row][col]) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let pivot_row = rows[rank].clone();
+        for (idx, row) in rows.iter_mut().enumerate() {
+            if idx != rank && row[col] {
+                row.iter_mut().zip(&pivot_row).for_each(|(x, &p)| *x ^= p);
+            }
+        }
+        rank += 1;
+    }
+
+    // A light left over that still has to be on has no button for it
+    rows[rank..].iter().all(|row| !row[buttons.len()])
+}
+
+/// One machine per line, e.g. `[.##.] (3) (1,3) (2) {3,5,4,7}`: the light
+/// diagram, the buttons, then the joltage targets.
+fn parse_machine(mut line: Line) -> Result<(Machine, Machine2), Error> {
+    // ignition from the diagram: "[.#.#]" -> [Off, On, Off, On]
+    let diagram = line.bracketed('[', ']')?.rest();
+    let ignition: Vec<Indicator> = diagram
+        .chars()
+        .map(|c| match c {
+            '.' => Ok(Indicator::Off),
+            '#' => Ok(Indicator::On),
+            _ => Err(line.error(diagram, "expected only '.' and '#' in the diagram")),
+        })
+        .collect::<Result<_, _>>()?;
+
+    // Keep each index's text so a bad one can be pointed at below
+    let mut buttons = Vec::new();
+    loop {
+        buttons.push(
+            line.bracketed('(', ')')?
+                .list(',', |index| index.consumed(Line::int::<usize>))?,
+        );
+        if line.peek() != Some('(') {
+            break;
+        }
+    }
+
+    let (target, target_text) =
+        line.consumed(|target| target.bracketed('{', '}')?.list(',', Line::int::<i32>))?;
+    line.end()?;
+
+    if target.len() != ignition.len() {
+        return Err(line.error(target_text, "expected one target per light"));
+    }
+    if let Some(&(_, token)) = buttons
+        .iter()
+        .flatten()
+        .find(|&&(index, _)| index >= ignition.len())
+    {
+        return Err(line.error(token, "button index is past the last light"));
+    }
+
+    let buttons: Vec<Sequence> = buttons
+        .into_iter()
+        .map(|button| button.into_iter().map(|(index, _)| index).collect())
+        .filter(|button: &Sequence| !button.is_empty())
+        .collect();
+
+    if !can_reach(&ignition, &buttons) {
+        return Err(line.error(
+            diagram,
+            "no set of button presses turns on exactly these lights",
+        ));
+    }
+
+    // indicators start all Off, same length as ignition
+    let lights = Machine {
+        indicators: vec![Indicator::Off; ignition.len()],
+        buttons: buttons.clone(),
+        ignition,
+    };
+    Ok((lights, Machine2 { target, buttons }))
+}
+
+/// Each machine read twice: as indicator lights for part 1 and as joltage
+/// counters for part 2.
+#[derive(Debug, Clone)]
+pub struct Machines {
+    pub lights: Vec<Machine>,
+    pub counters: Vec<Machine2>,
+}
+
+pub fn parse_machines(input: &str) -> Result<Machines, Error> {
+    let mut machines = Machines {
+        lights: Vec::new(),
+        counters: Vec::new(),
+    };
+    for line in parse::lines(input) {
+        if let Some((lights, counters)) = policy::line(parse_machine(line))? {
+            machines.lights.push(lights);
+            machines.counters.push(counters);
+        }
+    }
+
+    Ok(machines)
+}
+
+pub fn solve_part1(machines: &[Machine]) -> i64 {
+    let answer: i64 = machines.iter().map(Machine::min_moves_to_ignition).sum();
+
+    answer
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine2 {
+    target: Vec<i32>,
+    buttons: Vec<Vec<usize>>,
+}
+
+/// Note for anyyone reading, I got some serious help from chatgpt to implement the solver.
+impl Machine2 {
+    /// Build the linear system A * x = b for this machine.
+    ///
+    /// - A is d x m where d = counters, m = buttons
+    /// - A[i][j] = 1 if button j affects counter i, else 0
+    /// - b[i] = target[i]
+    pub fn build_linear_system(&self) -> (Vec<Vec<Rational>>, Vec<Rational>) {
+        let d = self.target.len(); // number of counters (equations)
+        let m = self.buttons.len(); // number of buttons (variables)
+
+        // Build A: d rows, m columns
+        let mut a = vec![vec![Rational::zero(); m]; d];
+
+        for (j, btn) in self.buttons.iter().enumerate() {
+            for &counter_idx in btn {
+                // for each counter touched by button j, set A[counter][j] = 1
+                a[counter_idx][j] = Rational::one();
+            }
+        }
+
+        // Build b: just target as rationals
+        let b = self
+            .target
+            .iter()
+            .map(|&t| Rational::from_i64(t as i64))
+            .collect();
+
+        (a, b)
+    }
+
+    pub fn rref_augmented(&self) -> (Vec<Vec<Rational>>, Vec<Option<usize>>) {
+        let (a, b) = self.build_linear_system();
+        let rows = a.len();
+        if rows == 0 {
+            return (Vec::new(), Vec::new());
+        }
+        let cols = a[0].len();
+
+        // Build augmented matrix [A | b]
+        let mut mat = Vec::with_capacity(rows);
+        for i in 0..rows {
+            let mut row = Vec::with_capacity(cols + 1);
+            row.extend_from_slice(&a[i]);
+            row.push(b[i]);
+            mat.push(row);
+        }
+
+        rref(mat)
+    }
+}
+
+impl Machine2 {
+    pub fn print_linear_system(&self) {
+        let (a, b) = self.build_linear_system();
+        let d = a.len(); // counters / equations
+        let m = if d > 0 { a[0].len() } else { 0 }; // buttons / variables
+
+        println!("Linear system A * x = b");
+        println!("Counters (d) = {}", d);
+        println!("Buttons  (m) = {}", m);
+        println!();
+
+        for i in 0..d {
+            let mut terms = Vec::new();
+
+            for (j, coeff) in a[i].iter().enumerate() {
+                if !coeff.is_zero() {
+                    terms.push(format!("x{}", j));
+                }
+            }
+
+            if terms.is_empty() {
+                // No button affects this counter.
+                println!(
+                    "Eq {:02}: 0 = {:?}",
+                    i,
+                    b[i], // Rational implements Debug
+                );
+            } else {
+                println!("Eq {:02}: {} = {:?}", i, terms.join(" + "), b[i],);
+            }
+        }
+
+        println!();
+    }
+}
+
+impl Machine2 {
+    pub fn min_presses_from_rref(
+        &self,
+        rref_mat: &[Vec<Rational>],
+        pivot_cols: &[Option<usize>],
+    ) -> Option<i64> {
+        // 1. Determine how many variables we have (buttons)
+        let m = self.buttons.len();
+        let rows = rref_mat.len();
+        if rows == 0 {
+            return Some(0); // degenerate
+        }
+
+        // 2. Which columns are pivots, which are free?
+        // pivot_row_for_col[j] = Some(row) if x_j is a pivot
+        let mut pivot_row_for_col = vec![None; m];
+        for (row, p) in pivot_cols.iter().enumerate() {
+            if let Some(col) = p
+                && *col < m
+            {
+                pivot_row_for_col[*col] = Some(row);
+            }
+        }
+
+        let free_cols: Vec<usize> = (0..m).filter(|&j| pivot_row_for_col[j].is_none()).collect();
+        let num_params = free_cols.len();
+
+        // 3. Build Expr for each x_j: x_j = constant + Σ coeff_k * param_k
+        // param k corresponds to free_cols[k].
+        #[derive(Clone)]
+        struct Expr {
+            coeffs: Vec<Rational>, // len = num_params
+            constant: Rational,
+        }
+
+        // Initialize all exprs to 0
+        let mut exprs: Vec<Expr> = (0..m)
+            .map(|_| Expr {
+                coeffs: vec![Rational::zero(); num_params],
+                constant: Rational::zero(),
+            })
+            .collect();
+
+        // Free variables: x_{free_cols[k]} = param_k
+        for (k, &col) in free_cols.iter().enumerate() {
+            exprs[col].coeffs[k] = Rational::one();
+            exprs[col].constant = Rational::zero();
+        }
+
+        // Helper: expr_p -= factor * expr_other
+        impl Expr {
+            fn sub_scaled(&mut self, other: &Expr, factor: Rational) {
+                for i in 0..self.coeffs.len() {
+                    self.coeffs[i] = self.coeffs[i] - factor * other.coeffs[i];
+                }
+                self.constant = self.constant - factor * other.constant;
+            }
+        }
+
+        let last_col = m; // augmented [A|b], so b is at column m
+
+        // For each pivot row, solve for that pivot variable in terms of free vars
+        for (row, p) in pivot_cols.iter().enumerate() {
+            let Some(pivot_col) = p else { continue };
+            let pivot_col = *pivot_col;
+            if pivot_col >= m {
+                continue;
+            }
+
+            let rhs = rref_mat[row][last_col];
+            let mut expr_p = Expr {
+                coeffs: vec![Rational::zero(); num_params],
+                constant: rhs,
+            };
+
+            // subtract contributions from free variables
+            for &free_col in free_cols.iter() {
+                let coeff = rref_mat[row][free_col];
+                if coeff.is_zero() {
+                    continue;
+                }
+                // x_p = rhs - coeff * x_free
+                expr_p.sub_scaled(&exprs[free_col], coeff);
+            }
+
+            exprs[pivot_col] = expr_p;
+        }
+
+        // 4. We now have x_j(params) = exprs[j].
+        //    Next, do a bounded integer search over params to find the min sum of presses.
+
+        // simple bound: params in [0, max_target]
+        let max_target = self.target.iter().copied().max().unwrap_or(0).max(0) as i64;
+
+        if num_params == 0 {
+            // fully determined, just evaluate once
+            let x_vals = (0..m).map(|j| exprs[j].constant).collect::<Vec<_>>();
+            let presses = rational_vec_to_presses(&x_vals)?;
+            let sum: i64 = presses.iter().sum();
+            return Some(sum);
+        }
+
+        // generic recursive search over all param combinations
+        let mut best_sum: Option<i64> = None;
+
+        fn rational_vec_to_presses(vals: &[Rational]) -> Option<Vec<i64>> {
+            let mut out = Vec::with_capacity(vals.len());
+            for v in vals {
+                // must be >= 0
+                if v.num < 0 {
+                    return None;
+                }
+                // must be integer → denominator == 1
+                if v.den != 1 {
+                    return None;
+                }
+                out.push(v.num);
+            }
+            Some(out)
+        }
+
+        // recursive search over parameters
+        fn search_params(
+            param_idx: usize,
+            params: &mut [i64],
+            exprs: &[Expr],
+            max_target: i64,
+            best_sum: &mut Option<i64>,
+        ) {
+            let num_params = params.len();
+            if param_idx == num_params {
+                // evaluate all x_j
+                let m = exprs.len();
+                let mut vals = Vec::with_capacity(m);
+                for expr in exprs {
+                    let mut v = expr.constant;
+                    for (k, &p) in params.iter().enumerate() {
+                        if !expr.coeffs[k].is_zero() && p != 0 {
+                            v = v + expr.coeffs[k] * Rational::from_i64(p);
+                        }
+                    }
+                    vals.push(v);
+                }
+
+                let presses = match rational_vec_to_presses(&vals) {
+                    Some(p) => p,
+                    None => return,
+                };
+
+                let sum: i64 = presses.iter().sum();
+                if let Some(cur_best) = best_sum
+                    && sum >= *cur_best
+                {
+                    return;
+                }
+                *best_sum = Some(sum);
+                return;
+            }
+
+            for v in 0..=max_target {
+                params[param_idx] = v;
+                // optional: you can add very cheap early pruning here if you like
+                search_params(param_idx + 1, params, exprs, max_target, best_sum);
+            }
+        }
+
+        let mut params = vec![0i64; num_params];
+        search_params(0, &mut params, &exprs, max_target, &mut best_sum);
+
+        best_sum
+    }
+}
+
+pub fn solve_part2(machines: &[Machine2]) -> i64 {
+    let mut total = 0_i64;
+    for m in machines {
+        let (rref_mat, pivot_cols) = m.rref_augmented();
+
+        let ans = m
+            .min_presses_from_rref(&rref_mat, &pivot_cols)
+            .expect("no non-negative integer solution for this machine");
+
+        total += ans;
+    }
+
+    total
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input<'a> = Machines;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Machines, Error> {
+        parse_machines(input)
+    }
+
+    fn part1(machines: &Machines) -> i64 {
+        solve_part1(&machines.lights)
+    }
+
+    fn part2(machines: &Machines) -> i64 {
+        solve_part2(&machines.counters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+    [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+    [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}"#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_machines(TEST_INPUT).unwrap().lights);
+        assert_eq!(result, 7);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_machines(TEST_INPUT).unwrap().counters);
+        assert_eq!(result, 33);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let err = parse_machines("[#.#] (1) (0,1) {1,1,1}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 2: no set of button presses turns on exactly these lights (found '#.#')"
+        );
+        // Pressing (0,1) then (1) reaches it
+        assert!(parse_machines("[#..] (1) (0,1) {1,1,1}").is_ok());
+    }
+}
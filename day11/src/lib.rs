@@ -0,0 +1,167 @@
+use utils::parse::{self, Line};
+use utils::{Error, Solution, policy, search};
+
+use std::collections::HashMap;
+
+pub type Device = String;
+
+pub type DeviceHash = HashMap<Device, Vec<Device>>;
+
+/// Number of routes the data can take from `you` to `out`
+pub fn you_to_out(devices: &DeviceHash) -> i64 {
+    let paths = search::count_paths("you".to_string(), search::adjacency(devices), |device| {
+        device == "out"
+    });
+    paths as i64
+}
+
+fn has_all_targets(parts: &[String], a: &str, b: &str, c: &str) -> bool {
+    parts.iter().any(|x| x == a) && parts.iter().any(|x| x == b) && parts.iter().any(|x| x == c)
+}
+
+fn build_device_string(parts: &[String], extra: Option<&str>, start: &str) -> String {
+    let mut out: Vec<String> = vec![start.to_string()];
+
+    out.extend(parts[1..].iter().cloned());
+
+    if let Some(extra_val) = extra
+        && !out.iter().any(|d| d == extra_val)
+    {
+        out.push(extra_val.to_string());
+    }
+
+    out.join(" ")
+}
+
+pub fn svr_to_out(devices: &DeviceHash) -> i64 {
+    fn solve(device: &str, devices: &DeviceHash, memo: &mut HashMap<String, i64>) -> i64 {
+        if let Some(&cached) = memo.get(device) {
+            return cached;
+        }
+
+        let device_with_targets: Vec<Device> = device
+            .split_whitespace()
+            .map(|tok| tok.to_string())
+            .collect();
+
+        let head = device_with_targets[0].as_str();
+
+        if head == "out" {
+            let ok = has_all_targets(&device_with_targets, "out", "dac", "fft");
+            let result = if ok { 1 } else { 0 };
+            memo.insert(device.to_string(), result);
+            return result;
+        }
+
+        let mut total = 0_i64;
+
+        if let Some(outputs) = devices.get(head) {
+            for output in outputs {
+                let next = match head {
+                    "dac" => build_device_string(&device_with_targets, Some("dac"), output),
+                    "fft" => build_device_string(&device_with_targets, Some("fft"), output),
+                    _ => build_device_string(&device_with_targets, None, output),
+                };
+
+                total += solve(&next, devices, memo);
+            }
+        }
+
+        memo.insert(device.to_string(), total);
+        total
+    }
+
+    let mut memo: HashMap<String, i64> = HashMap::new();
+    solve("svr", devices, &mut memo)
+}
+
+/// A device and the devices its outputs feed, e.g. `aaa: you hhh`.
+fn parse_device(mut line: Line) -> Result<(Device, Vec<Device>), Error> {
+    let src = line.key()?.to_string();
+    let outputs = line.many(|output| output.word().map(str::to_string))?;
+    Ok((src, outputs))
+}
+
+pub fn parse_devices(input: &str) -> Result<DeviceHash, Error> {
+    let mut map: DeviceHash = HashMap::new();
+
+    for line in parse::lines(input) {
+        if let Some((src, outputs)) = policy::line(parse_device(line))? {
+            map.insert(src, outputs);
+        }
+    }
+
+    Ok(map)
+}
+
+pub fn solve_part1(devices: &DeviceHash) -> i64 {
+    you_to_out(devices)
+}
+
+pub fn solve_part2(devices: &DeviceHash) -> i64 {
+    svr_to_out(devices)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input<'a> = DeviceHash;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<DeviceHash, Error> {
+        parse_devices(input)
+    }
+
+    fn part1(devices: &DeviceHash) -> i64 {
+        solve_part1(devices)
+    }
+
+    fn part2(devices: &DeviceHash) -> i64 {
+        solve_part2(devices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out"#;
+
+    const TEST_INPUT_2: &str = r#"svr: aaa bbb
+aaa: fft
+fft: ccc
+bbb: tty
+tty: ccc
+ccc: ddd eee
+ddd: hub
+hub: fff
+eee: dac
+dac: fff
+fff: ggg hhh
+ggg: out
+hhh: out"#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_devices(TEST_INPUT).unwrap());
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_devices(TEST_INPUT_2).unwrap());
+        assert_eq!(result, 2);
+    }
+}
@@ -1,224 +1,3 @@
-use std::time::Instant;
-use utils::read_input;
-
-#[derive(Debug, Clone)]
-struct Shape {
-    id: usize,
-    rows: Vec<String>,
-}
-
-#[derive(Debug, Clone)]
-struct Region {
-    w: usize,
-    h: usize,
-    counts: Vec<usize>,
-}
-
-fn solve_part1(input: &str) -> i64 {
-    let (shapes, regions) = parse_input(input);
-    let shape_sizes: Vec<usize> = shapes.iter().map(|s| count_hashes(&s.rows)).collect();
-
-    let mut pass_count = 0i64;
-
-    for r in &regions {
-        let region_area = r.w * r.h;
-
-        let used_area: usize = (0..shape_sizes.len())
-            .map(|i| shape_sizes[i] * r.counts.get(i).copied().unwrap_or(0))
-            .sum();
-
-        let leftover = region_area as i64 - used_area as i64;
-
-        // I just tweaked this till the tests passed, but it appears to solve the problem?
-        // After can confirm this works with a huge range on the data (0-360),
-        // Im deciding thats deliberate as christmas is the 360th day of the year
-        // 2 works for both the tests and the real data set
-        if leftover > 2 {
-            pass_count += 1;
-        }
-    }
-
-    pass_count
-}
-
-fn solve_part2(input: &str) -> &str {
-    "Merry christmas"
-}
-
-fn parse_input(input: &str) -> (Vec<Shape>, Vec<Region>) {
-    let mut lines = input.lines().map(|l| l.trim_end()).peekable();
-
-    let mut shapes: Vec<Shape> = Vec::new();
-    let mut regions: Vec<Region> = Vec::new();
-
-    while let Some(&line) = lines.peek() {
-        if line.is_empty() {
-            lines.next();
-            continue;
-        }
-        if looks_like_region_line(line) {
-            break;
-        }
-
-        let header = lines.next().unwrap();
-        let (id_str, _) = header
-            .split_once(':')
-            .expect("shape header should look like `N:`");
-        let id: usize = id_str.trim().parse().expect("shape id must be usize");
-
-        let mut rows: Vec<String> = Vec::new();
-        while let Some(&row) = lines.peek() {
-            if row.is_empty() || looks_like_region_line(row) {
-                break;
-            }
-            rows.push(lines.next().unwrap().to_string());
-        }
-
-        if let Some(&maybe_blank) = lines.peek() {
-            if maybe_blank.is_empty() {
-                lines.next();
-            }
-        }
-
-        shapes.push(Shape { id, rows });
-    }
-
-    shapes.sort_by_key(|s| s.id);
-    let max_id = shapes.last().map(|s| s.id).unwrap_or(0);
-
-    let mut normalized: Vec<Option<Shape>> = vec![None; max_id + 1];
-    for s in shapes.into_iter() {
-        let id = s.id;
-        normalized[id] = Some(s);
-    }
-
-    let shapes: Vec<Shape> = normalized
-        .into_iter()
-        .map(|opt| opt.expect("missing shape id in input"))
-        .collect();
-
-    while let Some(line) = lines.next() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-
-        let (dim, rest) = line
-            .split_once(':')
-            .expect("region line should contain ':'");
-
-        let (w_str, h_str) = dim
-            .split_once('x')
-            .expect("region dims should look like WxH");
-
-        let w: usize = w_str.trim().parse().unwrap();
-        let h: usize = h_str.trim().parse().unwrap();
-
-        let counts: Vec<usize> = rest
-            .split_whitespace()
-            .map(|n| n.parse().unwrap())
-            .collect();
-
-        regions.push(Region { w, h, counts });
-    }
-
-    (shapes, regions)
-}
-
-fn looks_like_region_line(line: &str) -> bool {
-    // e.g. "12x5: 1 0 1 0 2 2"
-    let line = line.trim();
-    if line.is_empty() {
-        return false;
-    }
-    let has_colon = line.contains(':');
-    let has_x = line.contains('x');
-    has_colon
-        && has_x
-        && line
-            .chars()
-            .next()
-            .map(|c| c.is_ascii_digit())
-            .unwrap_or(false)
-}
-
-fn count_hashes(rows: &[String]) -> usize {
-    rows.iter()
-        .map(|row| row.chars().filter(|&c| c == '#').count())
-        .sum()
-}
-
-fn solve(input: &str) -> (i64, &str) {
-    let part1 = solve_part1(input);
-    let part2 = solve_part2(input);
-    (part1, part2)
-}
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Loading input from ./inputs/day12.txt");
-
-    let input = read_input("./inputs/day12.txt")?;
-
-    let start = Instant::now();
-    let (part1, part2) = solve(&input);
-    let duration = start.elapsed();
-
-    println!("Part 1 {}", part1);
-    println!("Part 2 {}", part2);
-    println!("Execution time: {:?}", duration);
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = r#"
-0:
-###
-##.
-##.
-
-1:
-###
-##.
-.##
-
-2:
-.##
-###
-##.
-
-3:
-##.
-###
-##.
-
-4:
-###
-#..
-###
-
-5:
-###
-.#.
-###
-
-4x4: 0 0 0 0 2 0
-12x5: 1 0 1 0 2 2
-12x5: 1 0 1 0 3 2
-"#;
-
-    #[test]
-    fn test_part1() {
-        let result = solve_part1(TEST_INPUT);
-        assert_eq!(result, 2);
-    }
-
-    #[test]
-    fn test_part2() {
-        let result = solve_part2(TEST_INPUT);
-        assert_eq!(result, "Merry christmas");
-    }
+fn main() -> std::process::ExitCode {
+    utils::run::<day12::Day12>()
 }
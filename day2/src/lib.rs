@@ -0,0 +1,128 @@
+use utils::error::parse_at;
+use utils::{Error, Solution, policy};
+
+#[derive(Debug, Clone)]
+pub struct Id {
+    pub valid: bool,
+    pub id: i64,
+}
+
+impl Id {
+    pub fn new_part_a(value: i64) -> Self {
+        let s = value.to_string();
+        let len = s.len();
+
+        // Check the "exactly repeated twice" rule
+        let invalid = if len.is_multiple_of(2) {
+            let half = len / 2;
+            let (first, second) = s.split_at(half);
+            first == second
+        } else {
+            false
+        };
+
+        Self {
+            valid: !invalid,
+            id: value,
+        }
+    }
+
+    pub fn new_part_b(value: i64) -> Self {
+        let s = value.to_string();
+        let doubled = format!("{s}{s}");
+        let invalid = doubled[1..doubled.len() - 1].contains(&s);
+        Self {
+            valid: !invalid,
+            id: value,
+        }
+    }
+}
+
+fn parse_range(line_no: usize, line: &str, range: &str) -> Result<(i64, i64), Error> {
+    let (start, end) = range.split_once('-').ok_or_else(|| {
+        Error::at(
+            line_no,
+            line,
+            range.trim(),
+            "range must contain a single '-'",
+        )
+    })?;
+
+    let first = parse_at(line_no, line, start, "a number before the '-'")?;
+    let second = parse_at(line_no, line, end, "a number after the '-'")?;
+
+    Ok((first, second))
+}
+
+pub fn load_input(content: &str) -> Result<Vec<(i64, i64)>, Error> {
+    let mut ranges = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        for range in line.split(',').filter(|r| !r.trim().is_empty()) {
+            ranges.extend(policy::line(parse_range(idx + 1, line, range))?);
+        }
+    }
+
+    Ok(ranges)
+}
+
+fn sum_invalid(ranges: &[(i64, i64)], validator: fn(i64) -> Id) -> i64 {
+    ranges
+        .iter()
+        .flat_map(|&(start, end)| start..=end)
+        .map(validator)
+        .filter(|id| !id.valid)
+        .map(|id| id.id)
+        .sum()
+}
+
+pub fn solve_part1(ranges: &[(i64, i64)]) -> i64 {
+    sum_invalid(ranges, Id::new_part_a)
+}
+
+pub fn solve_part2(ranges: &[(i64, i64)]) -> i64 {
+    sum_invalid(ranges, Id::new_part_b)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Vec<(i64, i64)>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<(i64, i64)>, Error> {
+        load_input(input)
+    }
+
+    fn part1(ranges: &Vec<(i64, i64)>) -> i64 {
+        solve_part1(ranges)
+    }
+
+    fn part2(ranges: &Vec<(i64, i64)>) -> i64 {
+        solve_part2(ranges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+824824821-824824827,2121212118-2121212124";
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&load_input(TEST_INPUT).unwrap());
+        assert_eq!(result, 1227775554);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&load_input(TEST_INPUT).unwrap());
+        assert_eq!(result, 4174379265);
+    }
+}
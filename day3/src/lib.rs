@@ -0,0 +1,164 @@
+use utils::{Error, Solution, policy};
+
+/// Batteries turned on in each bank, per part
+const PART1_DIGITS: usize = 2;
+const PART2_DIGITS: usize = 12;
+
+#[derive(Debug, Clone)]
+pub struct BatteryBank {
+    pub batteries: Vec<u8>,
+}
+
+fn pick_n_digits(digits: &[u8], n: usize) -> (Vec<u8>, Vec<usize>) {
+    let mut chosen = Vec::with_capacity(n);
+    let mut indices = Vec::with_capacity(n);
+
+    let mut start = 0;
+
+    for pos in 0..n {
+        let remaining_slots = n - pos;
+        let last_allowed = digits.len() - remaining_slots;
+
+        let mut best_idx = start;
+        let mut best_val = digits[start];
+
+        for (offset, &v) in digits[start..=last_allowed].iter().enumerate() {
+            let i = start + offset;
+            if v > best_val {
+                best_val = v;
+                best_idx = i;
+            }
+        }
+
+        chosen.push(best_val);
+        indices.push(best_idx);
+
+        start = best_idx + 1;
+    }
+
+    (chosen, indices)
+}
+
+impl BatteryBank {
+    /// Build a bank from line `line_no` of the input, one digit per battery.
+    /// Every part must be able to turn on enough of them.
+    pub fn build(line_no: usize, line: &str) -> Result<Self, Error> {
+        let digits = line.trim();
+        let batteries: Vec<u8> = digits
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                    let token = &digits[i..i + c.len_utf8()];
+                    Error::at(line_no, line, token, "expected a digit")
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        if batteries.len() < PART2_DIGITS {
+            return Err(Error::at(
+                line_no,
+                line,
+                digits,
+                format!(
+                    "expected at least {PART2_DIGITS} batteries, found {}",
+                    batteries.len()
+                ),
+            ));
+        }
+        Ok(Self { batteries })
+    }
+
+    /// Largest number that can be made from `n` of the batteries, in order
+    pub fn largest_charge(&self, n: usize) -> u64 {
+        let (chosen_digits, _indices) = pick_n_digits(&self.batteries, n);
+
+        chosen_digits
+            .iter()
+            .fold(0_u64, |acc, &d| acc * 10 + d as u64)
+    }
+}
+
+pub fn lines_to_battery_banks(input: &str) -> Result<Vec<BatteryBank>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(idx, line)| policy::line(BatteryBank::build(idx + 1, line)).transpose())
+        .collect()
+}
+
+fn total_charge(banks: &[BatteryBank], n: usize) -> u64 {
+    banks.iter().map(|bank| bank.largest_charge(n)).sum()
+}
+
+/// Total of the largest 2 digit charge in each bank
+pub fn solve_part1(banks: &[BatteryBank]) -> u64 {
+    total_charge(banks, PART1_DIGITS)
+}
+
+/// Total of the largest 12 digit charge in each bank
+pub fn solve_part2(banks: &[BatteryBank]) -> u64 {
+    total_charge(banks, PART2_DIGITS)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input<'a> = Vec<BatteryBank>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<BatteryBank>, Error> {
+        lines_to_battery_banks(input)
+    }
+
+    fn part1(banks: &Vec<BatteryBank>) -> u64 {
+        solve_part1(banks)
+    }
+
+    fn part2(banks: &Vec<BatteryBank>) -> u64 {
+        solve_part2(banks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"987654321111111
+811111111111119
+234234234234278
+818181911112111"#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&lines_to_battery_banks(TEST_INPUT).unwrap());
+        assert_eq!(result, 357);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&lines_to_battery_banks(TEST_INPUT).unwrap());
+        assert_eq!(result, 3121910778619);
+    }
+
+    #[test]
+    fn test_short_bank() {
+        let err = lines_to_battery_banks("987654321111111\n 12").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected at least 12 batteries, found 2 (found '12')"
+        );
+    }
+
+    #[test]
+    fn test_not_a_digit() {
+        let err = lines_to_battery_banks("9876x4321111111").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 5: expected a digit (found 'x')"
+        );
+    }
+}
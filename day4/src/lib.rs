@@ -0,0 +1,114 @@
+use utils::animate::Recorder;
+use utils::render::{Render, Rgb};
+use utils::{Error, Grid, Solution, parse_char_grid};
+
+/// Parse the map of paper rolls (`@`) and empty floor (`.`).
+pub fn parse_grid(input: &str) -> Result<Grid<char>, Error> {
+    for (idx, line) in input.lines().enumerate() {
+        if let Some((i, c)) = line.char_indices().find(|(_, c)| !matches!(c, '@' | '.')) {
+            let token = &line[i..i + c.len_utf8()];
+            return Err(Error::at(idx + 1, line, token, "expected '@' or '.'"));
+        }
+    }
+
+    parse_char_grid(input)
+}
+
+pub fn solve_part1(grid: &Grid<char>) -> i64 {
+    grid.iter_cells()
+        .filter(|&(row, col, value)| {
+            let neighbours = grid.neighbors_8(row, col);
+            match value {
+                '@' => neighbours.filter(|&(_, _, v)| *v == '@').count() < 4,
+                '.' => false,
+                _ => false,
+            }
+        })
+        .count() as i64
+}
+
+pub fn solve_part2(grid: &Grid<char>) -> i64 {
+    let mut grid = grid.clone();
+    let mut count = 0;
+    // One roll at a time is thousands of frames, so only keep some
+    let mut frames = Recorder::new("day4-part2").every(25);
+    frames.record(&grid);
+
+    loop {
+        let maybe_item = grid.iter_cells().find(|&(row, col, value)| {
+            let neighbours = grid.neighbors_8(row, col);
+            match value {
+                '@' => neighbours.filter(|&(_, _, v)| *v == '@').count() < 4,
+                '.' => false,
+                _ => false,
+            }
+        });
+
+        match maybe_item {
+            Some((row, col, _)) => {
+                grid[row][col] = '.';
+                count += 1;
+                frames.record(&grid);
+            }
+            None => break,
+        }
+    }
+
+    frames.finish(
+        Render::new()
+            .colours(|&c| (c == '@').then_some(Rgb::GREEN))
+            .scale(4),
+    );
+    count
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Grid<char>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Grid<char>, Error> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Grid<char>) -> i64 {
+        solve_part1(grid)
+    }
+
+    fn part2(grid: &Grid<char>) -> i64 {
+        solve_part2(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@."#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_grid(TEST_INPUT).unwrap());
+        assert_eq!(result, 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_grid(TEST_INPUT).unwrap());
+        // 43 rolls can be removed from the puzzle's example
+        assert_eq!(result, 43);
+    }
+}
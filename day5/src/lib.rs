@@ -0,0 +1,159 @@
+use std::ops::RangeInclusive;
+use utils::parse::Section;
+use utils::{Error, Solution, parse_range_bounds, policy, sections};
+
+/// Split ranges and ingredients
+pub fn split_range_ingredients(input: &str) -> Result<(Section<'_>, Section<'_>), Error> {
+    let mut sections = sections(input);
+    match (sections.next(), sections.next(), sections.next()) {
+        (Some(ranges), Some(ingredients), None) => Ok((ranges, ingredients)),
+        _ => Err(Error::new(
+            "expected exactly two sections separated by a blank line",
+        )),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    pub ranges: Vec<RangeInclusive<i64>>,
+    pub ingredients: Vec<i64>,
+}
+
+/// Split the input into two parts, ranges and items
+pub fn parse_inventory(input: &str) -> Result<Inventory, Error> {
+    let (ranges_section, ingredients_section) = split_range_ingredients(input)?;
+
+    let ranges: Vec<RangeInclusive<i64>> = ranges_section
+        .lines()
+        .filter_map(|line| {
+            let range = parse_range_bounds::<i64>(line.text())
+                .ok_or_else(|| line.error(line.text(), "expected a range like 3-5"));
+            policy::line(range).transpose()
+        })
+        .collect::<Result<_, _>>()?;
+
+    let ingredients: Vec<i64> = ingredients_section
+        .lines()
+        .filter_map(|mut line| {
+            let id = line.int().and_then(|id| line.end().map(|_| id));
+            policy::line(id).transpose()
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Inventory {
+        ranges,
+        ingredients,
+    })
+}
+
+/// Iterate through ranges and then look through ingredients
+/// counting every ingredient that falls in one of them as fresh.
+pub fn solve_part1(inventory: &Inventory) -> i64 {
+    inventory
+        .ingredients
+        .iter()
+        .filter(|ingredient| {
+            inventory
+                .ranges
+                .iter()
+                .any(|range| range.contains(ingredient))
+        })
+        .count() as i64
+}
+
+/// Will sort the ranges by start value.
+/// Walk through them and fold in or extend the range.
+/// If we hit a gap then we start a new range.
+pub fn solve_part2(inventory: &Inventory) -> i64 {
+    let mut ranges = inventory.ranges.clone();
+
+    ranges.sort_by_key(|r| *r.start());
+
+    let mut merged: Vec<RangeInclusive<i64>> = Vec::new();
+
+    for r in ranges {
+        let start = *r.start();
+        let end = *r.end();
+
+        if let Some(last) = merged.last_mut() {
+            let last_start = *last.start();
+            let last_end = *last.end();
+
+            if start <= last_end + 1 {
+                if end > last_end {
+                    *last = last_start..=end;
+                }
+            } else {
+                merged.push(start..=end);
+            }
+        } else {
+            merged.push(start..=end);
+        }
+    }
+
+    merged
+        .iter()
+        .map(|r| *r.end() - *r.start() + 1)
+        .sum::<i64>()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input<'a> = Inventory;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Inventory, Error> {
+        parse_inventory(input)
+    }
+
+    fn part1(inventory: &Inventory) -> i64 {
+        solve_part1(inventory)
+    }
+
+    fn part2(inventory: &Inventory) -> i64 {
+        solve_part2(inventory)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32"#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_inventory(TEST_INPUT).unwrap());
+        assert_eq!(result, 3);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_inventory(TEST_INPUT).unwrap());
+        assert_eq!(result, 14);
+    }
+
+    #[test]
+    fn test_crlf() {
+        let input = TEST_INPUT
+            .replace('\n', "\r\n")
+            .replace("\r\n\r\n", "\r\n \r\n\r\n")
+            + "\r\n\r\n";
+        let result = solve_part1(&parse_inventory(&input).unwrap());
+        assert_eq!(result, 3);
+    }
+}
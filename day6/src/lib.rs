@@ -0,0 +1,300 @@
+use utils::{Error, Solution, transpose_matrix};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Mul,
+}
+
+impl Op {
+    fn apply(self, values: impl Iterator<Item = i64>) -> i64 {
+        match self {
+            Op::Add => values.sum(),
+            Op::Mul => values.product(),
+        }
+    }
+}
+
+/// The worksheet read two ways: part 1 splits each line on whitespace,
+/// part 2 keeps every character column so digits can be read top to bottom.
+#[derive(Debug, Clone)]
+pub struct Worksheet {
+    /// One row of numbers per line, one number per problem
+    pub numbers: Vec<Vec<i64>>,
+    /// The operator under each problem
+    pub operators: Vec<Op>,
+    /// The numbers of each problem read down its character columns, left to
+    /// right
+    pub columns: Vec<Vec<i64>>,
+}
+
+pub fn parse_worksheet(input: &str) -> Result<Worksheet, Error> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let Some((op_line, number_lines)) = lines.split_last() else {
+        return Err(Error::new("the worksheet is empty"));
+    };
+    let op_line_no = lines.len();
+
+    let operators = op_line
+        .split_whitespace()
+        .map(|token| match token {
+            "+" => Ok(Op::Add),
+            "*" => Ok(Op::Mul),
+            _ => Err(Error::at(
+                op_line_no,
+                op_line,
+                token,
+                "expected an operator, '+' or '*'",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let numbers = number_lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let row = line
+                .split_whitespace()
+                .map(|token| match token.bytes().all(|b| b.is_ascii_digit()) {
+                    true => token
+                        .parse::<i64>()
+                        .map_err(|_| Error::at(idx + 1, line, token, "number is too large")),
+                    false => Err(Error::at(idx + 1, line, token, "expected a number")),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if row.len() != operators.len() {
+                return Err(Error::at(
+                    idx + 1,
+                    line,
+                    line.trim(),
+                    format!("expected {} numbers, one per problem", operators.len()),
+                ));
+            }
+            Ok(row)
+        })
+        .collect::<Result<_, _>>()?;
+
+    let grid = parse_grid(&lines);
+    if grid.iter().any(|row| row.len() != operators.len()) {
+        return Err(Error::at(
+            op_line_no,
+            op_line,
+            op_line.trim(),
+            "expected each operator under the first column of its problem",
+        ));
+    }
+
+    // Each block is one problem; drop its operator row and read the digits
+    // column by column instead
+    let mut columns = Vec::with_capacity(operators.len());
+    let mut start = 0;
+    for block in transpose_matrix(&grid) {
+        let digit_rows = transpose_matrix(&block[..block.len() - 1]);
+        let values = digit_rows
+            .iter()
+            .enumerate()
+            .map(|(offset, digits)| row_to_value(digits, number_lines, start + offset))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Skip the blank separator column too
+        start += digit_rows.len() + 1;
+        columns.push(values);
+    }
+
+    Ok(Worksheet {
+        numbers,
+        operators,
+        columns,
+    })
+}
+
+pub fn solve_part1(worksheet: &Worksheet) -> i64 {
+    worksheet
+        .operators
+        .iter()
+        .enumerate()
+        .map(|(i, op)| op.apply(worksheet.numbers.iter().map(|row| row[i])))
+        .sum()
+}
+
+fn parse_grid(lines: &[&str]) -> Vec<Vec<Vec<String>>> {
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let max_width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let operator_row_idx = lines.len() - 1;
+    let operator_line = lines[operator_row_idx];
+    let operator_chars: Vec<char> = operator_line.chars().collect();
+
+    let mut operator_positions = Vec::new();
+    for (idx, ch) in operator_chars.iter().enumerate() {
+        if !ch.is_whitespace() {
+            operator_positions.push(idx);
+        }
+    }
+
+    let mut is_separator = vec![false; max_width];
+    for pair in operator_positions.windows(2) {
+        let right = pair[1];
+        if right > 0 {
+            let sep_col = right - 1;
+            let ch_at_sep = operator_chars.get(sep_col).copied().unwrap_or(' ');
+            if ch_at_sep == ' ' {
+                is_separator[sep_col] = true;
+            }
+        }
+    }
+
+    let mut result: Vec<Vec<Vec<String>>> = Vec::with_capacity(lines.len());
+
+    for (row_idx, line) in lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut row_groups: Vec<Vec<String>> = Vec::new();
+        let mut current_group: Vec<String> = Vec::new();
+
+        for (col, &separator) in is_separator.iter().enumerate() {
+            if separator {
+                if !current_group.is_empty() {
+                    row_groups.push(current_group);
+                    current_group = Vec::new();
+                }
+                continue;
+            }
+
+            let raw_ch = chars.get(col).copied().unwrap_or(' ');
+
+            if row_idx == operator_row_idx {
+                if !raw_ch.is_whitespace() {
+                    current_group.push(raw_ch.to_string());
+                }
+            } else {
+                let ch = if raw_ch == ' ' { '0' } else { raw_ch };
+                current_group.push(ch.to_string());
+            }
+        }
+
+        if !current_group.is_empty() {
+            row_groups.push(current_group);
+        }
+
+        result.push(row_groups);
+    }
+
+    result
+}
+
+/// The number read down character column `col` of `number_lines`, whose
+/// digits top to bottom are `digits`
+fn row_to_value(digits: &[String], number_lines: &[&str], col: usize) -> Result<i64, Error> {
+    let mut end = digits.len();
+    while end > 1 && digits[end - 1] == "0" {
+        end -= 1;
+    }
+
+    let s = digits[..end].join("");
+    if s.is_empty() {
+        return Ok(0);
+    }
+    s.parse::<i64>().map_err(|_| {
+        let too_large = s.bytes().all(|b| b.is_ascii_digit());
+        let message = match too_large {
+            true => "number read down this column is too large",
+            false => "expected a digit",
+        };
+        // Point at the top of the number, or at the character that isn't a
+        // digit
+        number_lines
+            .iter()
+            .enumerate()
+            .find_map(|(idx, line)| {
+                let (i, c) = line.char_indices().nth(col)?;
+                let found = match too_large {
+                    true => c != ' ',
+                    false => c != ' ' && !c.is_ascii_digit(),
+                };
+                found.then(|| Error::at(idx + 1, line, &line[i..i + c.len_utf8()], message))
+            })
+            .unwrap_or_else(|| Error::new(message))
+    })
+}
+
+pub fn solve_part2(worksheet: &Worksheet) -> i64 {
+    worksheet
+        .columns
+        .iter()
+        .zip(&worksheet.operators)
+        .map(|(values, op)| op.apply(values.iter().rev().copied()))
+        .sum()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input<'a> = Worksheet;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Worksheet, Error> {
+        parse_worksheet(input)
+    }
+
+    fn part1(worksheet: &Worksheet) -> i64 {
+        solve_part1(worksheet)
+    }
+
+    fn part2(worksheet: &Worksheet) -> i64 {
+        solve_part2(worksheet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"123 328  51 64
+ 45 64  387 23
+  6 98  215 314
+*   +   *   +  "#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_worksheet(TEST_INPUT).unwrap());
+        assert_eq!(result, 4277556);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_worksheet(TEST_INPUT).unwrap());
+        assert_eq!(result, 3263827);
+    }
+
+    #[test]
+    fn test_trailing_blank_lines() {
+        let worksheet = parse_worksheet(&format!("{TEST_INPUT}\n\n")).unwrap();
+        assert_eq!(solve_part1(&worksheet), 4277556);
+        assert_eq!(solve_part2(&worksheet), 3263827);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_worksheet("12 3\n4\t 5\n+  *").unwrap_err();
+        let loc = err.location.unwrap();
+        assert_eq!((loc.line, loc.column, loc.token.as_str()), (2, 2, "\t"));
+
+        let tall = "9\n".repeat(20) + "+";
+        let err = parse_worksheet(&tall).unwrap_err();
+        assert_eq!(err.message, "number read down this column is too large");
+        assert_eq!(err.location.unwrap().line, 1);
+    }
+}
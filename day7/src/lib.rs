@@ -0,0 +1,314 @@
+use bitvec::prelude::*;
+use std::collections::HashMap;
+use std::fmt;
+use utils::animate::Recorder;
+use utils::render::{Render, Rgb};
+use utils::{Error, Grid, Solution};
+
+// DP state: map beam patterns to number of ways to reach them
+type Pattern = BitVec;
+type Count = u128;
+type Level = HashMap<Pattern, Count>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Splitter,
+    Beam,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        match c {
+            '.' => Ok(Cell::Empty),
+            '^' => Ok(Cell::Splitter),
+            'S' => Ok(Cell::Beam),
+            _ => Err(c),
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::Splitter => '^',
+            Cell::Beam => '|',
+        };
+        write!(f, "{c}")
+    }
+}
+
+pub fn parse_grid(input: &str) -> Result<Grid<Cell>, Error> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let data = lines
+        .into_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            line.char_indices()
+                .map(|(i, c)| {
+                    Cell::try_from(c).map_err(|c| {
+                        let token = &line[i..i + c.len_utf8()];
+                        Error::at(idx + 1, line, token, "expected '.', '^' or 'S'")
+                    })
+                })
+                .collect()
+        })
+        .collect::<Result<Vec<Vec<Cell>>, _>>()?;
+    Grid::from_rows(data)
+}
+
+fn simulate_row(grid: &mut Grid<Cell>, row: usize) -> i64 {
+    let mut changes: Vec<(usize, usize, Cell)> = Vec::new();
+    let mut split_count = 0;
+
+    for col in 0..grid.width() {
+        if let Some(&Cell::Beam) = grid.get(row, col) {
+            let below_row = row + 1;
+
+            match grid.get(below_row, col) {
+                Some(&Cell::Empty) => {
+                    changes.push((below_row, col, Cell::Beam));
+                }
+                Some(&Cell::Splitter) => {
+                    let mut split_happened = false;
+
+                    if let Some(&cell_left) = grid.get(below_row, col.saturating_sub(1))
+                        && col > 0
+                        && cell_left != Cell::Beam
+                    {
+                        changes.push((below_row, col - 1, Cell::Beam));
+                        split_happened = true;
+                    }
+
+                    if let Some(&cell_right) = grid.get(below_row, col + 1)
+                        && cell_right != Cell::Beam
+                    {
+                        changes.push((below_row, col + 1, Cell::Beam));
+                        split_happened = true;
+                    }
+
+                    if split_happened {
+                        split_count += 1;
+                    }
+                }
+                Some(&Cell::Beam) | None => {}
+            }
+        }
+    }
+
+    for (r, c, new_cell) in changes {
+        if let Some(cell) = grid.get_mut(r, c) {
+            *cell = new_cell;
+        }
+    }
+
+    split_count
+}
+
+pub fn solve_part1(grid: &Grid<Cell>) -> i64 {
+    let mut grid = grid.clone();
+    let mut total_splits = 0;
+    let mut frames = Recorder::new("day7-part1");
+    frames.record(&grid);
+
+    for row in 0..grid.height() {
+        total_splits += simulate_row(&mut grid, row);
+        frames.record(&grid);
+    }
+
+    frames.finish(
+        Render::new()
+            .colours(|cell| match cell {
+                Cell::Empty => None,
+                Cell::Splitter => Some(Rgb::RED),
+                Cell::Beam => Some(Rgb::YELLOW),
+            })
+            .scale(4),
+    );
+    total_splits
+}
+
+fn initial_row_beams(grid: &Grid<Cell>) -> Pattern {
+    let cols = grid.width();
+    let mut bits = bitvec![0; cols];
+    let row = 0;
+    for col in 0..cols {
+        if let Some(&Cell::Beam) = grid.get(row, col) {
+            bits.set(col, true);
+        }
+    }
+    bits
+}
+
+fn count_splits_for_row(
+    base_grid: &Grid<Cell>,
+    row: usize,
+    cols: usize,
+    row_beams: &Pattern,
+) -> usize {
+    if row + 1 >= base_grid.height() {
+        return 0;
+    }
+    let mut splits = 0;
+    for col in 0..cols {
+        if row_beams[col]
+            && let Some(&Cell::Splitter) = base_grid.get(row + 1, col)
+        {
+            splits += 1;
+        }
+    }
+    splits
+}
+
+fn advance_row_with_choice(
+    base_grid: &Grid<Cell>,
+    row: usize,
+    cols: usize,
+    row_beams: &Pattern,
+    choice_mask: usize, // Each bit = left(1) or right(0) for each splitter
+) -> Pattern {
+    let mut next_row = bitvec![0; cols];
+    if row + 1 >= base_grid.height() {
+        return next_row;
+    }
+
+    let mut split_idx = 0;
+    for col in 0..cols {
+        if !row_beams[col] {
+            continue;
+        }
+
+        match base_grid.get(row + 1, col) {
+            Some(&Cell::Splitter) => {
+                // Check bit to decide left or right
+                let go_left = (choice_mask & (1 << split_idx)) != 0;
+                split_idx += 1;
+
+                if go_left && col > 0 {
+                    next_row.set(col - 1, true);
+                } else if !go_left && col + 1 < cols {
+                    next_row.set(col + 1, true);
+                }
+            }
+            Some(&Cell::Empty) => {
+                next_row.set(col, true);
+            }
+            _ => {}
+        }
+    }
+    next_row
+}
+
+pub fn solve_part2(base_grid: &Grid<Cell>) -> i64 {
+    let rows = base_grid.height();
+    let cols = base_grid.width();
+
+    let initial = initial_row_beams(base_grid);
+
+    let mut current_level: Level = HashMap::new();
+    current_level.insert(initial, 1);
+
+    // Process row by row, tracking unique patterns + counts
+    for row in 0..rows {
+        let mut next_level: Level = HashMap::new();
+
+        for (row_beams, &ways) in current_level.iter() {
+            if row_beams.not_any() {
+                continue;
+            }
+
+            let k = count_splits_for_row(base_grid, row, cols, row_beams);
+
+            if k == 0 {
+                // No splits, deterministic path
+                let next_pattern = advance_row_with_choice(base_grid, row, cols, row_beams, 0);
+                *next_level.entry(next_pattern).or_insert(0) += ways;
+            } else {
+                // k splits means 2^k possible choices
+                let combinations = 1usize << k;
+                for choice_mask in 0..combinations {
+                    let next_pattern =
+                        advance_row_with_choice(base_grid, row, cols, row_beams, choice_mask);
+                    // Merge duplicate patterns, accumulate counts
+                    *next_level.entry(next_pattern).or_insert(0) += ways;
+                }
+            }
+        }
+
+        current_level = next_level;
+        if current_level.is_empty() {
+            break;
+        }
+    }
+
+    current_level.values().copied().sum::<Count>() as i64
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Grid<Cell>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Grid<Cell>, Error> {
+        parse_grid(input)
+    }
+
+    fn part1(grid: &Grid<Cell>) -> i64 {
+        solve_part1(grid)
+    }
+
+    fn part2(grid: &Grid<Cell>) -> i64 {
+        solve_part2(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#".......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+..............."#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_grid(TEST_INPUT).unwrap());
+        assert_eq!(result, 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_grid(TEST_INPUT).unwrap());
+        assert_eq!(result, 40);
+    }
+
+    #[test]
+    fn test_trailing_blank_lines() {
+        let grid = parse_grid(&format!("{TEST_INPUT}\n\n")).unwrap();
+        assert_eq!(grid, parse_grid(TEST_INPUT).unwrap());
+    }
+}
@@ -0,0 +1,241 @@
+use utils::error::parse_at;
+use utils::geom::Point3;
+use utils::{Error, Solution, policy};
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    dist_sq: i64,
+    i: usize,
+    j: usize,
+}
+
+struct Circuits {
+    circuit: Vec<usize>,
+}
+
+impl Circuits {
+    fn new(n: usize) -> Self {
+        // each point starts as its own circuit
+        let mut circuit = Vec::with_capacity(n);
+        for i in 0..n {
+            circuit.push(i);
+        }
+
+        Self { circuit }
+    }
+
+    // find the root of a node
+    fn find(&self, mut x: usize) -> usize {
+        while self.circuit[x] != x {
+            x = self.circuit[x];
+        }
+        x
+    }
+
+    // merge two circuits
+    fn merge(&mut self, a: usize, b: usize) {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+
+        if root_a != root_b {
+            // attach root_b's chain under root_a
+            self.circuit[root_b] = root_a;
+        }
+    }
+
+    // count how big each circuit is
+    fn sizes(&self) -> Vec<usize> {
+        let n = self.circuit.len();
+        let mut counts = vec![0usize; n];
+
+        // walk each node up to its root
+        for i in 0..n {
+            let root = self.find(i);
+            counts[root] += 1;
+        }
+
+        // filter out empty ones
+        counts.into_iter().filter(|&x| x > 0).collect()
+    }
+}
+
+fn dist_sq(a: Point3<i64>, b: Point3<i64>) -> i64 {
+    let d = a - b;
+    d.dot(d)
+}
+
+fn parse_point(line_no: usize, line: &str) -> Result<Point3<i64>, Error> {
+    let fields: Vec<&str> = line.split(',').collect();
+    let [x, y, z] = fields[..] else {
+        return Err(Error::at(
+            line_no,
+            line,
+            line,
+            "expected three coordinates, 'x,y,z'",
+        ));
+    };
+    Ok(Point3::new(
+        parse_at(line_no, line, x, "a coordinate")?,
+        parse_at(line_no, line, y, "a coordinate")?,
+        parse_at(line_no, line, z, "a coordinate")?,
+    ))
+}
+
+/// Part 1 multiplies the sizes of the three largest circuits, so there must
+/// be at least three junction boxes
+const MIN_POINTS: usize = 3;
+
+pub fn parse_points(input: &str) -> Result<Vec<Point3<i64>>, Error> {
+    let points: Vec<_> = input
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| policy::line(parse_point(idx + 1, line)).transpose())
+        .collect::<Result<_, _>>()?;
+    if points.len() < MIN_POINTS {
+        return Err(Error::new(format!(
+            "expected at least {MIN_POINTS} junction boxes, found {}",
+            points.len()
+        )));
+    }
+    Ok(points)
+}
+
+/// Product of the sizes of the three largest circuits after connecting the
+/// `num` closest pairs, or of every circuit if there are fewer than three
+pub fn solve_part1(points: &[Point3<i64>], num: usize) -> u64 {
+    let n = points.len();
+    let num_edges = n * n.saturating_sub(1) / 2;
+    let mut edges = Vec::with_capacity(num_edges);
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let d2 = dist_sq(points[i], points[j]);
+            edges.push(Edge { dist_sq: d2, i, j });
+        }
+    }
+
+    edges.sort_by_key(|e| e.dist_sq);
+
+    let mut dsu = Circuits::new(points.len());
+
+    for edge in edges.iter().take(num) {
+        dsu.merge(edge.i, edge.j);
+    }
+
+    let mut sizes = dsu.sizes();
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    sizes.iter().take(3).map(|&size| size as u64).product()
+}
+
+pub fn solve_part2(points: &[Point3<i64>]) -> i64 {
+    let n = points.len();
+    let num_edges = n * n.saturating_sub(1) / 2;
+    let mut edges = Vec::with_capacity(num_edges);
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let d2 = dist_sq(points[i], points[j]);
+            edges.push(Edge { dist_sq: d2, i, j });
+        }
+    }
+
+    edges.sort_by_key(|e| e.dist_sq);
+
+    let mut dsu = Circuits::new(points.len());
+
+    let mut i = 0;
+
+    let mut final_breaker_1 = Point3::default();
+    let mut final_breaker_2 = Point3::default();
+    while i < edges.len() {
+        let edge = &edges[i];
+        dsu.merge(edge.i, edge.j);
+        i += 1;
+
+        // check if fully connected
+        let sizes = dsu.sizes();
+        if sizes.contains(&n) {
+            final_breaker_1 = points[edge.i];
+            final_breaker_2 = points[edge.j];
+            break;
+        }
+    }
+
+    final_breaker_1.x * final_breaker_2.x
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input<'a> = Vec<Point3<i64>>;
+    type Part1 = u64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Point3<i64>>, Error> {
+        parse_points(input)
+    }
+
+    fn part1(points: &Vec<Point3<i64>>) -> u64 {
+        solve_part1(points, 1000)
+    }
+
+    fn part2(points: &Vec<Point3<i64>>) -> i64 {
+        solve_part2(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689"#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_points(TEST_INPUT).unwrap(), 10);
+        assert_eq!(result, 40);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_points(TEST_INPUT).unwrap());
+        assert_eq!(result, 25272);
+    }
+
+    #[test]
+    fn test_few_points() {
+        for input in ["", "1,2,3\n4,5,6\n"] {
+            let err = parse_points(input).unwrap_err();
+            assert!(
+                err.message
+                    .starts_with("expected at least 3 junction boxes")
+            );
+        }
+
+        // Every pair connected leaves a single circuit
+        let points = parse_points("0,0,0\n1,0,0\n5,0,0").unwrap();
+        assert_eq!(solve_part1(&points, 3), 3);
+    }
+}
@@ -0,0 +1,225 @@
+use utils::animate::Recorder;
+use utils::error::parse_at;
+use utils::geom::Point2;
+use utils::render::{Render, Rgb};
+use utils::{Compressor, Connectivity, Error, Grid, Solution, policy};
+
+fn parse_point(line_no: usize, line: &str) -> Result<Point2<i64>, Error> {
+    let fields: Vec<&str> = line.split(',').collect();
+    let [x, y] = fields[..] else {
+        return Err(Error::at(
+            line_no,
+            line,
+            line,
+            "expected two coordinates, 'x,y'",
+        ));
+    };
+    Ok(Point2::new(
+        parse_at(line_no, line, x, "a coordinate")?,
+        parse_at(line_no, line, y, "a coordinate")?,
+    ))
+}
+
+pub fn parse_points(input: &str) -> Result<Vec<Point2<i64>>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| policy::line(parse_point(idx + 1, line)).transpose())
+        .collect()
+}
+
+pub fn solve_part1(points: &[Point2<i64>]) -> i64 {
+    let mut max_volume = 0_i64;
+
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            let d = points[i] - points[j];
+            if d.x != 0 && d.y != 0 {
+                let x_length = d.x.abs() + 1;
+                let y_length = d.y.abs() + 1;
+                let volume = x_length * y_length;
+
+                if volume > max_volume {
+                    max_volume = volume;
+                }
+            }
+        }
+    }
+
+    max_volume
+}
+
+fn rectangle_is_inside(
+    grid: &Grid<char>,
+    row_min: usize,
+    row_max: usize,
+    col_min: usize,
+    col_max: usize,
+) -> bool {
+    for r in row_min..=row_max {
+        for c in col_min..=col_max {
+            if let Some(ch) = grid.get(r, c)
+                && *ch == '.'
+            {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+pub fn solve_part2(points: &[Point2<i64>]) -> i64 {
+    // Compress the world coordinates to one cell per distinct x and y
+    let xs = Compressor::new(points.iter().map(|p| p.x));
+    let ys = Compressor::new(points.iter().map(|p| p.y));
+
+    // Build compressed points, every one of which is on both axes
+    let compressed_points: Vec<Point2<usize>> = points
+        .iter()
+        .map(|p| Point2::new(xs.index(p.x).unwrap(), ys.index(p.y).unwrap()))
+        .collect();
+
+    // Compressed grid size
+    let width = xs.len();
+    let height = ys.len();
+
+    let mut grid = Grid::filled(height, width, '.');
+
+    // Set the original points as '#'
+    for &p in &compressed_points {
+        grid[p] = '#';
+    }
+    // Connect each point to the next with 'X'
+    for i in 0..compressed_points.len() {
+        let a = compressed_points[i];
+        let b = compressed_points[(i + 1) % compressed_points.len()];
+
+        if a.x == b.x {
+            // same column make a vertical segment
+            for y in a.y.min(b.y)..=a.y.max(b.y) {
+                if let Some(cell) = grid.at_mut(Point2::new(a.x, y))
+                    && *cell != '#'
+                {
+                    *cell = 'X';
+                }
+            }
+        } else if a.y == b.y {
+            // same row make a horizontal segment
+            for x in a.x.min(b.x)..=a.x.max(b.x) {
+                if let Some(cell) = grid.at_mut(Point2::new(x, a.y))
+                    && *cell != '#'
+                {
+                    *cell = 'X';
+                }
+            }
+        }
+    }
+
+    // Flood fill from outside on the compressed grid
+    let outside = grid.exterior(|&c| c == '.', Connectivity::Four);
+
+    // Any '.' that was not visited is enclosed fill with 'X'
+    for p in grid.points() {
+        if !outside[p] && grid[p] == '.' {
+            grid[p] = 'X';
+        }
+    }
+
+    let mut best_area: i64 = 0;
+
+    for i in 0..compressed_points.len() {
+        for j in (i + 1)..compressed_points.len() {
+            let a = compressed_points[i];
+            let b = compressed_points[j];
+
+            if a.x == b.x || a.y == b.y {
+                continue;
+            }
+
+            let row_min = a.y.min(b.y);
+            let row_max = a.y.max(b.y);
+            let col_min = a.x.min(b.x);
+            let col_max = a.x.max(b.x);
+
+            // check if this rectangle is fully inside
+            if rectangle_is_inside(&grid, row_min, row_max, col_min, col_max) {
+                // convert back to real world.
+                let world_x_min = xs.value(col_min);
+                let world_x_max = xs.value(col_max);
+                let world_y_min = ys.value(row_min);
+                let world_y_max = ys.value(row_max);
+
+                let width = (world_x_max - world_x_min).abs() + 1;
+                let height = (world_y_max - world_y_min).abs() + 1;
+                let area = width * height;
+
+                if area > best_area {
+                    best_area = area;
+                }
+            }
+        }
+    }
+
+    // With --visualize, show the compressed shape: its outline and inside
+    let mut frames = Recorder::new("day9-part2");
+    frames.record(&grid);
+    frames.finish(
+        Render::new()
+            .colours(|&c| match c {
+                '#' => Some(Rgb::RED),
+                'X' => Some(Rgb::GREEN),
+                _ => None,
+            })
+            .scale(4),
+    );
+
+    best_area
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Vec<Point2<i64>>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Point2<i64>>, Error> {
+        parse_points(input)
+    }
+
+    fn part1(points: &Vec<Point2<i64>>) -> i64 {
+        solve_part1(points)
+    }
+
+    fn part2(points: &Vec<Point2<i64>>) -> i64 {
+        solve_part2(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = r#"7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3"#;
+
+    #[test]
+    fn test_part1() {
+        let result = solve_part1(&parse_points(TEST_INPUT).unwrap());
+        assert_eq!(result, 50);
+    }
+
+    #[test]
+    fn test_part2() {
+        let result = solve_part2(&parse_points(TEST_INPUT).unwrap());
+        assert_eq!(result, 24);
+    }
+}
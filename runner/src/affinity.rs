@@ -0,0 +1,35 @@
+/// Pin the calling thread to a single CPU, so a benchmark isn't migrated
+/// between cores halfway through.
+#[cfg(target_os = "linux")]
+pub fn pin_to_cpu(cpu: usize) -> Result<(), String> {
+    // Matches glibc's `cpu_set_t`: a bitmask of 1024 CPUs
+    const SET_WORDS: usize = 1024 / 64;
+
+    unsafe extern "C" {
+        fn sched_setaffinity(pid: i32, cpusetsize: usize, mask: *const u64) -> i32;
+    }
+
+    if cpu >= SET_WORDS * 64 {
+        return Err(format!("CPU {cpu} is out of range"));
+    }
+
+    let mut mask = [0u64; SET_WORDS];
+    mask[cpu / 64] |= 1 << (cpu % 64);
+
+    // SAFETY: `mask` is a valid, fully initialised cpu_set_t sized buffer and
+    // pid 0 means the calling thread
+    let result = unsafe { sched_setaffinity(0, size_of_val(&mask), mask.as_ptr()) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "failed to pin to CPU {cpu}: {}",
+            std::io::Error::last_os_error()
+        ))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_cpu(_cpu: usize) -> Result<(), String> {
+    Err("CPU pinning is only supported on Linux".to_string())
+}
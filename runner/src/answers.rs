@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use utils::input::from_root;
+use utils::{Answer, read_input};
+
+/// Expected answers per day and part, loaded from `answers.toml`.
+///
+/// Only the small subset of TOML the file needs is understood:
+///
+/// ```toml
+/// # comments
+/// [day12]
+/// part1 = 1234
+/// part2 = "Merry christmas"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(u8, u8), Answer>,
+}
+
+fn parse_day_header(header: &str) -> Option<u8> {
+    header.strip_prefix("day")?.parse().ok()
+}
+
+fn parse_part_key(key: &str) -> Option<u8> {
+    match key {
+        "part1" => Some(1),
+        "part2" => Some(2),
+        _ => None,
+    }
+}
+
+fn parse_value(value: &str) -> Option<Answer> {
+    if let Some(inner) = value.strip_prefix('"') {
+        let inner = inner.strip_suffix('"')?;
+        return Some(Answer::Text(
+            inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        ));
+    }
+    // TOML allows `_` as a digit separator, which is handy for long answers
+    value.replace('_', "").parse().ok().map(Answer::Int)
+}
+
+/// Drop a trailing `# comment`, ignoring any `#` inside a quoted string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = Answers::default();
+        let mut day: Option<u8> = None;
+
+        for (idx, raw) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = strip_comment(raw).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| format!("line {line_no}: unterminated section header"))?;
+                day = Some(parse_day_header(header.trim()).ok_or_else(|| {
+                    format!("line {line_no}: expected a section like [day1], got [{header}]")
+                })?);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {line_no}: expected `key = value`"))?;
+            let day =
+                day.ok_or_else(|| format!("line {line_no}: answer outside a [dayN] section"))?;
+            let part = parse_part_key(key.trim()).ok_or_else(|| {
+                format!(
+                    "line {line_no}: expected part1 or part2, got '{}'",
+                    key.trim()
+                )
+            })?;
+            let answer = parse_value(value.trim()).ok_or_else(|| {
+                format!(
+                    "line {line_no}: expected an integer or a quoted string, got '{}'",
+                    value.trim()
+                )
+            })?;
+
+            answers.expected.insert((day, part), answer);
+        }
+
+        Ok(answers)
+    }
+
+    /// Load the answers file, resolving a relative path against the workspace root.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let path = from_root(path);
+        let path = path.to_string_lossy();
+        let text = read_input(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        Self::parse(&text).map_err(|e| format!("{path}: {e}"))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.expected.get(&(day, part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            r#"
+# Expected answers
+[day1]
+part1 = 1_234 # trailing comment
+part2 = 42
+
+[day12]
+part2 = "Merry # christmas"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(1, 1), Some(&Answer::Int(1234)));
+        assert_eq!(answers.get(1, 2), Some(&Answer::Int(42)));
+        assert_eq!(answers.get(12, 1), None);
+        assert_eq!(
+            answers.get(12, 2),
+            Some(&Answer::Text("Merry # christmas".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Answers::parse("part1 = 3").unwrap_err();
+        assert_eq!(err, "line 1: answer outside a [dayN] section");
+
+        let err = Answers::parse("[day1]\npart3 = 3").unwrap_err();
+        assert_eq!(err, "line 2: expected part1 or part2, got 'part3'");
+    }
+}
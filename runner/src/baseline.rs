@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use utils::input::from_root;
+
+use crate::bench::Stats;
+
+/// Baselines live next to the build output, the same way criterion keeps its
+/// history in `target/criterion`, so they never end up in version control.
+const BASELINE_DIR: &str = "target/runner-baselines";
+
+const HEADER: &str = "day,part,min,median,mean,p95,stddev,outliers";
+
+pub type Snapshot = HashMap<(u8, u8), Stats>;
+
+fn path_for(name: &str) -> Result<PathBuf, String> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(format!(
+            "invalid baseline name '{name}', use letters, digits, '-', '_' or '.'"
+        ));
+    }
+    Ok(from_root(BASELINE_DIR).join(format!("{name}.csv")))
+}
+
+pub fn to_csv(results: &[(u8, u8, Stats)]) -> String {
+    let mut out = format!("{HEADER}\n");
+    for (day, part, s) in results {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{}",
+            day, part, s.min, s.median, s.mean, s.p95, s.stddev, s.outliers
+        );
+    }
+    out
+}
+
+pub fn from_csv(text: &str) -> Result<Snapshot, String> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header == HEADER => {}
+        _ => return Err("unrecognised baseline header".to_string()),
+    }
+
+    let mut snapshot = Snapshot::new();
+    for (idx, line) in lines {
+        let bad = || format!("line {}: malformed baseline row", idx + 1);
+        let fields: Vec<&str> = line.split(',').collect();
+        let [day, part, min, median, mean, p95, stddev, outliers] = fields[..] else {
+            return Err(bad());
+        };
+        let num = |s: &str| s.parse::<f64>().map_err(|_| bad());
+
+        let stats = Stats {
+            min: num(min)?,
+            median: num(median)?,
+            mean: num(mean)?,
+            p95: num(p95)?,
+            stddev: num(stddev)?,
+            outliers: outliers.parse().map_err(|_| bad())?,
+        };
+        let day = day.parse().map_err(|_| bad())?;
+        let part = part.parse().map_err(|_| bad())?;
+        snapshot.insert((day, part), stats);
+    }
+
+    Ok(snapshot)
+}
+
+pub fn save(name: &str, results: &[(u8, u8, Stats)]) -> Result<PathBuf, String> {
+    let path = path_for(name)?;
+    let dir = from_root(BASELINE_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    fs::write(&path, to_csv(results))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+pub fn load(name: &str) -> Result<Snapshot, String> {
+    let path = path_for(name)?;
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read baseline {}: {}", path.display(), e))?;
+    from_csv(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Slower,
+    Faster,
+    Unchanged,
+}
+
+/// Change from `before` to `after` in percent, 0 if there's nothing to
+/// compare against.
+pub fn delta_percent(before: f64, after: f64) -> f64 {
+    if before > 0.0 {
+        (after - before) / before * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Classify the change in median against the baseline, returning the change
+/// in percent alongside it. `threshold` is in percent as well.
+pub fn classify(baseline: &Stats, current: &Stats, threshold: f64) -> (Change, f64) {
+    let delta = delta_percent(baseline.median, current.median);
+
+    let change = if delta > threshold {
+        Change::Slower
+    } else if delta < -threshold {
+        Change::Faster
+    } else {
+        Change::Unchanged
+    };
+
+    (change, delta)
+}
+
+/// Print every day/part whose median moved by more than the threshold.
+pub fn print_comparison(
+    name: &str,
+    baseline: &Snapshot,
+    results: &[(u8, u8, Stats)],
+    threshold: f64,
+) {
+    println!("\nCompared to baseline '{name}' (threshold {threshold}%):");
+
+    let mut unchanged = 0;
+    let mut missing = 0;
+
+    for (day, part, current) in results {
+        let Some(before) = baseline.get(&(*day, *part)) else {
+            missing += 1;
+            continue;
+        };
+
+        let (change, delta) = classify(before, current, threshold);
+        let label = match change {
+            Change::Slower => "SLOWER",
+            Change::Faster => "FASTER",
+            Change::Unchanged => {
+                unchanged += 1;
+                continue;
+            }
+        };
+
+        println!(
+            "Day {:2} part {}: {:.3} ms -> {:.3} ms ({:+.1}%) {}",
+            day, part, before.median, current.median, delta, label
+        );
+    }
+
+    println!("{unchanged} unchanged");
+    if missing > 0 {
+        println!("{missing} not in the baseline");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(median: f64) -> Stats {
+        Stats {
+            min: median,
+            median,
+            mean: median,
+            p95: median,
+            stddev: 0.0,
+            outliers: 0,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let results = vec![(4, 1, stats(1.5)), (4, 2, stats(20.25))];
+        let snapshot = from_csv(&to_csv(&results)).unwrap();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[&(4, 1)], stats(1.5));
+        assert_eq!(snapshot[&(4, 2)], stats(20.25));
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(&stats(10.0), &stats(12.0), 5.0).0, Change::Slower);
+        assert_eq!(classify(&stats(10.0), &stats(8.0), 5.0).0, Change::Faster);
+        assert_eq!(
+            classify(&stats(10.0), &stats(10.4), 5.0).0,
+            Change::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_invalid_name() {
+        assert!(path_for("../main").is_err());
+        assert!(path_for("main-2").is_ok());
+    }
+}
@@ -0,0 +1,121 @@
+use std::hint::black_box;
+use std::panic;
+use utils::Parts;
+
+use crate::registry::Entry;
+
+/// Summary of a set of timing samples, all in ms.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub stddev: f64,
+    /// Samples outside the Tukey fences (1.5 × IQR beyond the quartiles)
+    pub outliers: usize,
+}
+
+/// Linearly interpolated percentile of already sorted samples, `p` in 0..=1.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = if sorted.len() > 1 {
+            sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let fence = 1.5 * (q3 - q1);
+        let outliers = sorted
+            .iter()
+            .filter(|&&s| s < q1 - fence || s > q3 + fence)
+            .count();
+
+        Self {
+            min: sorted[0],
+            median: percentile(&sorted, 0.5),
+            mean,
+            p95: percentile(&sorted, 0.95),
+            stddev: variance.sqrt(),
+            outliers,
+        }
+    }
+}
+
+/// Warm up and then repeatedly run the selected parts of a day, returning
+/// `(part, stats)` for each. Only the time spent in each part counts; the
+/// parse is repeated every iteration but not included.
+pub fn bench_day(
+    entry: &Entry,
+    input: &str,
+    parts: Parts,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Vec<(u8, Stats)>, String> {
+    let result = panic::catch_unwind(|| {
+        // A parse error shows up on the first run, so check once up front
+        (entry.run)(input, parts).map_err(|e| format!("Invalid input: {e}"))?;
+
+        for _ in 0..warmup {
+            let _ = black_box((entry.run)(black_box(input), parts));
+        }
+
+        let mut samples = [Vec::new(), Vec::new()];
+        for _ in 0..iterations {
+            let Ok(timings) = black_box((entry.run)(black_box(input), parts)) else {
+                continue;
+            };
+            for (part, samples) in (1..=2).zip(&mut samples) {
+                if let Some(result) = timings.part(part) {
+                    samples.push(result.time.as_secs_f64() * 1000.0);
+                }
+            }
+        }
+
+        Ok((1..=2)
+            .zip(samples)
+            .filter(|(_, samples)| !samples.is_empty())
+            .map(|(part, samples)| (part, Stats::from_samples(&samples)))
+            .collect())
+    });
+
+    result.unwrap_or_else(|_| Err(format!("day{} panicked", entry.day)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[4.0, 1.0, 3.0, 2.0, 5.0]);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.p95, 4.8);
+        assert_eq!(stats.stddev, 2.5_f64.sqrt());
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn test_outliers() {
+        let stats = Stats::from_samples(&[1.0, 1.1, 0.9, 1.0, 1.05, 0.95, 9.0]);
+        assert_eq!(stats.outliers, 1);
+    }
+}
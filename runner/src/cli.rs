@@ -0,0 +1,389 @@
+use std::time::Duration;
+
+use utils::Parts;
+use utils::policy::Policy;
+
+pub const USAGE: &str = "\
+Usage: runner [verify | bench | report] [options]
+       runner new <DAY>
+
+Modes:
+  (default)            Run every day once and print the performance table
+  verify               Run every day and compare against the expected answers
+  bench, --bench       Time each day and part repeatedly and report statistics
+  report               Run every day once and render a Markdown or HTML report
+  new <DAY>            Create dayN/ from the template and add it to the workspace,
+                       the runner and answers.toml
+
+Options:
+  --part <P>           Only run part 1, part 2 or both (default both)
+  --timeout <SECS>     Kill a day that runs longer than this, 0 for no limit (default 60)
+  --memory <MB>        Cap the memory each day may use (Linux only)
+  --strict             Fail a day on the first input line it can't parse (default)
+  --lenient            Skip input lines that can't be parsed and list them after the run
+
+Table options:
+  --format <FORMAT>    Output format: table, json or csv (default table)
+  --alloc-stats        Count allocations per part and show a memory table
+
+Verify options:
+  --answers <PATH>     Expected answers file (default answers.toml in the workspace root)
+  --jobs <N>           Run up to N days at the same time (default 1)
+
+Bench options (days always run one at a time):
+  --warmup <N>         Untimed iterations before measuring (default 3)
+  --iterations <N>     Measured iterations per day and part (default 10)
+  --save-baseline <NAME>
+                       Save the results as a named baseline
+  --baseline <NAME>    Compare the results against a saved baseline
+  --threshold <PCT>    Median change that counts as slower/faster (default 5)
+  --cpu <N>            Pin the benchmark to one CPU core (Linux only)
+
+Report options:
+  --format <FORMAT>    Report format: md or html (default md)
+  --redact             Leave the answers out of the report
+  --baseline <NAME>    Show the change in each part's time against a saved baseline
+
+  -h, --help           Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Table,
+    Bench,
+    Verify,
+    Report,
+    /// Scaffold a new day crate
+    New(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub mode: Mode,
+    pub format: Format,
+    pub parts: Parts,
+    pub answers: String,
+    /// Days to run concurrently, verify mode only
+    pub jobs: usize,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
+    /// Percent change in median that counts as a regression or improvement
+    pub threshold: f64,
+    /// CPU core to pin the benchmark to
+    pub cpu: Option<usize>,
+    /// How long a day may run before it's killed, covering every iteration
+    /// in bench mode
+    pub timeout: Option<Duration>,
+    pub memory_mb: Option<u64>,
+    /// What days do with input lines they can't parse
+    pub policy: Policy,
+    /// Count allocations with `utils::alloc`, table mode only
+    pub alloc_stats: bool,
+    /// Leave answers out of reports
+    pub redact: bool,
+    /// Set in the child processes the runner starts, see `worker`
+    pub worker: Option<u8>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Table,
+            format: Format::Table,
+            parts: Parts::Both,
+            answers: "answers.toml".to_string(),
+            jobs: 1,
+            warmup: 3,
+            iterations: 10,
+            save_baseline: None,
+            baseline: None,
+            threshold: 5.0,
+            cpu: None,
+            timeout: Some(Duration::from_secs(60)),
+            memory_mb: None,
+            policy: Policy::Strict,
+            alloc_stats: false,
+            redact: false,
+            worker: None,
+        }
+    }
+}
+
+/// Result of parsing the command line: either options to run with, or a
+/// request to print the usage text.
+pub enum Parsed {
+    Run(Options),
+    Help,
+}
+
+fn parse_format(value: Option<String>) -> Result<Format, String> {
+    match value.as_deref() {
+        Some("table") => Ok(Format::Table),
+        Some("json") => Ok(Format::Json),
+        Some("csv") => Ok(Format::Csv),
+        Some("md" | "markdown") => Ok(Format::Markdown),
+        Some("html") => Ok(Format::Html),
+        Some(other) => Err(format!(
+            "unknown format '{other}', expected table, json, csv, md or html"
+        )),
+        None => Err("--format needs a value".to_string()),
+    }
+}
+
+fn parse_count(flag: &str, value: Option<String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| format!("{flag} needs a value"))?;
+    value
+        .parse()
+        .map_err(|_| format!("{flag} expects a whole number, got '{value}'"))
+}
+
+fn parse_name(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{flag} needs a value"))
+}
+
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Parsed, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    let mut policy = None;
+
+    while let Some(arg) = args.next() {
+        // Allow both `--flag value` and `--flag=value`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next());
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Parsed::Help),
+            "verify" => options.mode = Mode::Verify,
+            "report" => options.mode = Mode::Report,
+            "bench" | "--bench" => options.mode = Mode::Bench,
+            "new" => {
+                let value = args.next().ok_or("new needs a day")?;
+                let day = value.trim_start_matches("day");
+                options.mode = Mode::New(
+                    day.parse()
+                        .map_err(|_| format!("new expects a day number, got '{value}'"))?,
+                );
+            }
+            "--strict" | "--lenient" => {
+                let chosen = match flag.as_str() {
+                    "--strict" => Policy::Strict,
+                    _ => Policy::Lenient,
+                };
+                if policy.is_some_and(|p| p != chosen) {
+                    return Err("--strict and --lenient can't be used together".to_string());
+                }
+                policy = Some(chosen);
+            }
+            "--format" => options.format = parse_format(value())?,
+            "--alloc-stats" => options.alloc_stats = true,
+            "--redact" => options.redact = true,
+            "--part" => options.parts = parse_name(&flag, value())?.parse()?,
+            "--answers" => options.answers = parse_name(&flag, value())?,
+            "--jobs" => options.jobs = parse_count(&flag, value())?,
+            "--cpu" => options.cpu = Some(parse_count(&flag, value())?),
+            "--timeout" => {
+                let secs = parse_count(&flag, value())?;
+                options.timeout = (secs > 0).then(|| Duration::from_secs(secs as u64));
+            }
+            "--memory" => options.memory_mb = Some(parse_count(&flag, value())? as u64),
+            "--worker" => {
+                let value = parse_name(&flag, value())?;
+                options.worker = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("{flag} expects a day, got '{value}'"))?,
+                );
+            }
+            "--warmup" => options.warmup = parse_count(&flag, value())?,
+            "--iterations" => options.iterations = parse_count(&flag, value())?,
+            "--save-baseline" => options.save_baseline = Some(parse_name(&flag, value())?),
+            "--baseline" => options.baseline = Some(parse_name(&flag, value())?),
+            "--threshold" => {
+                let value = parse_name(&flag, value())?;
+                options.threshold = value
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| format!("{flag} expects a percentage, got '{value}'"))?;
+            }
+            _ => return Err(format!("unknown argument '{flag}'")),
+        }
+    }
+
+    options.policy = policy.unwrap_or_default();
+
+    match (options.mode, options.format) {
+        (Mode::Table, Format::Markdown | Format::Html) => {
+            return Err("md and html are report formats, use `runner report`".to_string());
+        }
+        (Mode::Report, Format::Table) => options.format = Format::Markdown,
+        (Mode::Report, Format::Json | Format::Csv) => {
+            return Err("reports can only be rendered as md or html".to_string());
+        }
+        (Mode::Table | Mode::Report, _) | (_, Format::Table) => {}
+        _ => return Err("--format only applies to the performance table and reports".to_string()),
+    }
+
+    if options.mode != Mode::Bench && options.save_baseline.is_some() {
+        return Err("--save-baseline only applies to bench mode".to_string());
+    }
+    if !matches!(options.mode, Mode::Bench | Mode::Report) && options.baseline.is_some() {
+        return Err("--baseline only applies to bench and report modes".to_string());
+    }
+
+    if options.mode != Mode::Report && options.redact {
+        return Err("--redact only applies to report mode".to_string());
+    }
+
+    // Timings taken while other days run alongside are meaningless, so
+    // concurrency is kept to verify
+    if options.mode != Mode::Verify && options.jobs != 1 {
+        return Err("--jobs only applies to verify mode".to_string());
+    }
+    if options.jobs == 0 {
+        return Err("--jobs must be at least 1".to_string());
+    }
+
+    // Counting allocations slows every allocation down, so keep it out of
+    // bench runs
+    if options.mode == Mode::Bench && options.alloc_stats {
+        return Err("--alloc-stats doesn't apply to bench mode".to_string());
+    }
+
+    if options.mode != Mode::Bench && options.cpu.is_some() {
+        return Err("--cpu only applies to bench mode".to_string());
+    }
+
+    if options.memory_mb == Some(0) {
+        return Err("--memory must be at least 1".to_string());
+    }
+
+    if options.iterations == 0 {
+        return Err("--iterations must be at least 1".to_string());
+    }
+
+    Ok(Parsed::Run(options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(args: &str) -> Result<Options, String> {
+        match parse(args.split_whitespace().map(String::from))? {
+            Parsed::Run(options) => Ok(options),
+            Parsed::Help => Err("help".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let options = run("").unwrap();
+        assert_eq!((options.mode, options.format), (Mode::Table, Format::Table));
+        assert_eq!(options.timeout, Some(Duration::from_secs(60)));
+
+        let options = run("verify --jobs 4 --answers=mine.toml --lenient").unwrap();
+        assert_eq!(options.mode, Mode::Verify);
+        assert_eq!(options.jobs, 4);
+        assert_eq!(options.answers, "mine.toml");
+        assert_eq!(options.policy, Policy::Lenient);
+
+        let options =
+            run("bench --cpu=2 --warmup 1 --iterations 5 --save-baseline main --threshold 10%")
+                .unwrap();
+        assert_eq!(options.mode, Mode::Bench);
+        assert_eq!(options.cpu, Some(2));
+        assert_eq!((options.warmup, options.iterations), (1, 5));
+        assert_eq!(options.save_baseline.as_deref(), Some("main"));
+        assert_eq!(options.threshold, 10.0);
+
+        let options = run("report --redact --baseline main").unwrap();
+        assert_eq!(options.format, Format::Markdown);
+        assert!(options.redact);
+        assert_eq!(run("report --format html").unwrap().format, Format::Html);
+
+        let options = run("--format json --alloc-stats --part 2 --timeout 0 --memory 512").unwrap();
+        assert_eq!(options.format, Format::Json);
+        assert!(options.alloc_stats);
+        assert_eq!(options.parts, Parts::Two);
+        assert_eq!(options.timeout, None);
+        assert_eq!(options.memory_mb, Some(512));
+
+        assert_eq!(run("new day7").unwrap().mode, Mode::New(7));
+        assert_eq!(run("--strict --strict").unwrap().policy, Policy::Strict);
+        assert!(matches!(
+            parse(["verify".to_string(), "--help".to_string()]),
+            Ok(Parsed::Help)
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("--jobs 2", "--jobs only applies to verify mode"),
+            ("bench --jobs 2", "--jobs only applies to verify mode"),
+            ("verify --jobs 0", "--jobs must be at least 1"),
+            ("--cpu 1", "--cpu only applies to bench mode"),
+            ("verify --cpu 1", "--cpu only applies to bench mode"),
+            (
+                "verify --save-baseline a",
+                "--save-baseline only applies to bench mode",
+            ),
+            (
+                "verify --baseline a",
+                "--baseline only applies to bench and report modes",
+            ),
+            ("--redact", "--redact only applies to report mode"),
+            (
+                "bench --alloc-stats",
+                "--alloc-stats doesn't apply to bench mode",
+            ),
+            (
+                "--strict --lenient",
+                "--strict and --lenient can't be used together",
+            ),
+            (
+                "--format md",
+                "md and html are report formats, use `runner report`",
+            ),
+            (
+                "report --format csv",
+                "reports can only be rendered as md or html",
+            ),
+            (
+                "verify --format json",
+                "--format only applies to the performance table and reports",
+            ),
+            (
+                "--format xml",
+                "unknown format 'xml', expected table, json, csv, md or html",
+            ),
+            ("--memory 0", "--memory must be at least 1"),
+            ("bench --iterations 0", "--iterations must be at least 1"),
+            (
+                "--timeout soon",
+                "--timeout expects a whole number, got 'soon'",
+            ),
+            ("--timeout", "--timeout needs a value"),
+            ("--threshold x", "--threshold expects a percentage, got 'x'"),
+            ("new", "new needs a day"),
+            ("new tomorrow", "new expects a day number, got 'tomorrow'"),
+            ("--fast", "unknown argument '--fast'"),
+        ];
+        for (args, message) in cases {
+            assert_eq!(run(args).err().as_deref(), Some(message), "runner {args}");
+        }
+    }
+}
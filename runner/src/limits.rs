@@ -0,0 +1,39 @@
+/// Cap the address space of the current process, so a day that allocates
+/// without bound fails its allocation instead of dragging the machine into
+/// swap.
+#[cfg(target_os = "linux")]
+pub fn limit_memory(mb: u64) -> Result<(), String> {
+    const RLIMIT_AS: i32 = 9;
+
+    #[repr(C)]
+    struct Rlimit {
+        cur: u64,
+        max: u64,
+    }
+
+    unsafe extern "C" {
+        fn setrlimit(resource: i32, rlim: *const Rlimit) -> i32;
+    }
+
+    let bytes = mb.saturating_mul(1024 * 1024);
+    let limit = Rlimit {
+        cur: bytes,
+        max: bytes,
+    };
+
+    // SAFETY: `limit` is a valid `struct rlimit` for the duration of the call
+    let result = unsafe { setrlimit(RLIMIT_AS, &limit) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "failed to limit memory to {mb} MB: {}",
+            std::io::Error::last_os_error()
+        ))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn limit_memory(_mb: u64) -> Result<(), String> {
+    Err("memory limits are only supported on Linux".to_string())
+}
@@ -1,92 +1,219 @@
-use std::process::Command;
-use std::time::Instant;
-
-fn parse_internal_time(output: &str) -> Option<f64> {
-    // Look for "Execution time: <value><unit>"
-    for line in output.lines() {
-        if line.contains("Execution time:") {
-            // Extract the time portion
-            if let Some(time_part) = line.split("Execution time:").nth(1) {
-                let time_str = time_part.trim();
-
-                // Handle different formats: "123.456ms", "1.234s", "789µs", "1.234567s"
-                if time_str.ends_with("ms") {
-                    let value = time_str.trim_end_matches("ms").parse::<f64>().ok()?;
-                    return Some(value);
-                } else if time_str.ends_with("µs") {
-                    let value = time_str.trim_end_matches("µs").parse::<f64>().ok()?;
-                    return Some(value / 1000.0); // Convert µs to ms
-                } else if time_str.ends_with('s') {
-                    let value = time_str.trim_end_matches('s').parse::<f64>().ok()?;
-                    return Some(value * 1000.0); // Convert s to ms
-                }
+mod affinity;
+mod answers;
+mod baseline;
+mod bench;
+mod cli;
+mod limits;
+mod output;
+mod pool;
+mod record;
+mod registry;
+mod report;
+mod scaffold;
+mod verify;
+mod worker;
+
+use std::process::ExitCode;
+
+use answers::Answers;
+use bench::Stats;
+use cli::{Format, Mode, Options, Parsed};
+use record::Record;
+use utils::alloc::CountingAlloc;
+use utils::input;
+
+// Only counts once `--alloc-stats` switches it on in a worker
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+fn load_input(day: u8) -> Result<String, String> {
+    input::load_day(day).map_err(|e| format!("Failed to read input: {}", e))
+}
+
+/// Run every day in its own worker process, up to `jobs` of them at once.
+/// Records come back in day order either way.
+fn run_all(options: &Options, jobs: usize) -> Vec<Record> {
+    pool::map_ordered(&registry::days(), jobs, |entry| {
+        worker::run_day(entry.day, options)
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn run_table(options: &Options) {
+    let records = run_all(options, 1);
+
+    match options.format {
+        Format::Table => {
+            output::print_table(&records);
+            if options.alloc_stats {
+                output::print_memory_table(&records);
             }
         }
+        Format::Json => println!("{}", output::to_json(&records)),
+        Format::Csv => print!("{}", output::to_csv(&records)),
+        Format::Markdown | Format::Html => unreachable!("report formats are rejected by cli"),
     }
-    None
+    output::print_skipped(&records);
 }
 
-fn run_day(day: u8) -> Result<(u128, Option<f64>, String), String> {
-    let binary_path = format!("./target/release/day{}", day);
+fn run_report(options: &Options) -> ExitCode {
+    let snapshot = match options.baseline.as_deref().map(baseline::load).transpose() {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
 
-    let start = Instant::now();
-    let output = Command::new(&binary_path)
-        .output()
-        .map_err(|e| format!("Failed to execute day{}: {}", day, e))?;
-    let duration = start.elapsed();
+    let records = run_all(options, 1);
+    let report_options = report::ReportOptions {
+        redact: options.redact,
+        baseline: options.baseline.as_deref().zip(snapshot.as_ref()),
+    };
 
-    if !output.status.success() {
-        return Err(format!("day{} exited with non-zero status", day));
+    match options.format {
+        Format::Html => print!("{}", report::html(&records, &report_options)),
+        _ => print!("{}", report::markdown(&records, &report_options)),
     }
+    output::print_skipped(&records);
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let internal_time = parse_internal_time(&stdout);
-
-    Ok((duration.as_millis(), internal_time, stdout))
+    ExitCode::SUCCESS
 }
 
-fn main() {
-    println!("Advent of Code 2025 - Performance Table");
-    println!("========================================\n");
+fn run_bench(options: &Options) -> ExitCode {
+    // Load the baseline up front so a typo doesn't cost a whole bench run
+    let compare_to = match options.baseline.as_deref().map(baseline::load).transpose() {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
 
-    // Days to run (excluding day 10)
-    let days = [1, 2, 3, 4, 5, 6, 7, 8, 9, 11];
+    if let Some(cpu) = options.cpu
+        && let Err(e) = affinity::pin_to_cpu(cpu)
+    {
+        eprintln!("error: {e}");
+        return ExitCode::FAILURE;
+    }
+
+    println!("Advent of Code 2025 - Benchmark");
+    println!("===============================\n");
+    println!(
+        "{} warmup + {} measured iterations per part, parse excluded, times in ms",
+        options.warmup, options.iterations
+    );
+    match options.cpu {
+        Some(cpu) => println!("Days run one at a time, pinned to CPU {cpu}\n"),
+        None => println!("Days run one at a time\n"),
+    }
 
-    let mut results = Vec::new();
+    let mut results: Vec<(u8, u8, Stats)> = Vec::new();
 
-    for day in days {
-        match run_day(day) {
-            Ok((external_ms, internal_ms, _output)) => {
-                results.push((day, external_ms, internal_ms));
-            }
+    for entry in registry::days() {
+        match worker::bench_day(entry.day, options) {
+            Ok(stats) => results.extend(stats.into_iter().map(|(part, s)| (entry.day, part, s))),
+            Err(e) => println!("Day {:2}: {}", entry.day, e),
+        }
+    }
+
+    println!(
+        "┌──────┬──────┬───────────┬───────────┬───────────┬───────────┬───────────┬──────────┐"
+    );
+    println!(
+        "│ Day  │ Part │ Min       │ Median    │ Mean      │ p95       │ Std dev   │ Outliers │"
+    );
+    println!(
+        "├──────┼──────┼───────────┼───────────┼───────────┼───────────┼───────────┼──────────┤"
+    );
+
+    for (day, part, s) in &results {
+        // Flag rows with outliers so a noisy measurement stands out
+        let flag = if s.outliers > 0 { "!" } else { " " };
+        println!(
+            "│ {:>4} │ {:>4} │ {:>9.3} │ {:>9.3} │ {:>9.3} │ {:>9.3} │ {:>9.3} │ {:>7}{} │",
+            day, part, s.min, s.median, s.mean, s.p95, s.stddev, s.outliers, flag
+        );
+    }
+
+    println!(
+        "└──────┴──────┴───────────┴───────────┴───────────┴───────────┴───────────┴──────────┘"
+    );
+
+    if let (Some(name), Some(snapshot)) = (&options.baseline, &compare_to) {
+        baseline::print_comparison(name, snapshot, &results, options.threshold);
+    }
+
+    if let Some(name) = &options.save_baseline {
+        match baseline::save(name, &results) {
+            Ok(path) => println!("\nSaved baseline '{}' to {}", name, path.display()),
             Err(e) => {
-                println!("Day {:2}: ERROR - {}", day, e);
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
             }
         }
     }
 
-    // Print table header
-    println!("┌──────┬──────────────┐");
-    println!("│ Day  │ Time         │");
-    println!("├──────┼──────────────┤");
+    ExitCode::SUCCESS
+}
+
+fn run_verify(options: &Options) -> ExitCode {
+    let answers = match Answers::load(&options.answers) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
 
-    let mut total_internal = 0.0;
+    let records = run_all(options, options.jobs);
+    let summary = verify::check(&records, &answers);
+    output::print_skipped(&records);
 
-    for (day, _external_ms, internal_ms_opt) in &results {
-        if let Some(internal_ms) = internal_ms_opt {
-            total_internal += internal_ms;
-            println!("│ {:>4} │ {:>9.2} ms │", day, internal_ms);
-        } else {
-            println!("│ {:>4} │ {:>12} │", day, "N/A");
+    if summary.ok() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match cli::parse(std::env::args().skip(1)) {
+        Ok(Parsed::Run(options)) => options,
+        Ok(Parsed::Help) => {
+            println!("{}", cli::USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", cli::USAGE);
+            return ExitCode::from(2);
         }
+    };
+
+    if let Some(day) = options.worker {
+        return match registry::days().iter().find(|entry| entry.day == day) {
+            Some(entry) => worker::serve(entry, &options),
+            None => {
+                eprintln!("error: no solution registered for day {day}");
+                ExitCode::FAILURE
+            }
+        };
+    }
 
-        // Add day 10 placeholder after day 9
-        if *day == 9 {
-            println!("│   10 │  (unfinished)│");
+    match options.mode {
+        Mode::Table => run_table(&options),
+        Mode::Bench => return run_bench(&options),
+        Mode::Verify => return run_verify(&options),
+        Mode::Report => return run_report(&options),
+        Mode::New(day) => {
+            if let Err(e) = scaffold::new_day(day) {
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
+            }
         }
     }
 
-    println!("├──────┼──────────────┤");
-    println!("│Total │ {:>9.2} ms │", total_internal);
-    println!("└──────┴──────────────┘");
+    ExitCode::SUCCESS
 }
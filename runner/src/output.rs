@@ -0,0 +1,348 @@
+use std::fmt::Write;
+
+use utils::Answer;
+use utils::alloc::AllocStats;
+
+use crate::record::{Record, Status};
+
+/// Bumped whenever a field is renamed, removed or changes meaning, so
+/// consumers of the JSON and CSV output can tell when they need updating.
+pub const SCHEMA_VERSION: u32 = 4;
+
+pub fn print_table(records: &[Record]) {
+    println!("Advent of Code 2025 - Performance Table");
+    println!("========================================\n");
+
+    for day in records.chunk_by(|a, b| a.day == b.day) {
+        if let Status::Error(e) = &day[0].status {
+            println!("Day {:2}: ERROR - {}", day[0].day, e);
+        }
+    }
+
+    // Print table header
+    println!(
+        "┌──────┬──────────────────┬──────────────────┬────────────┬────────────┬────────────┬────────────┐"
+    );
+    println!(
+        "│ Day  │ Part 1           │ Part 2           │ Parse ms   │ Part 1 ms  │ Part 2 ms  │ Total ms   │"
+    );
+    println!(
+        "├──────┼──────────────────┼──────────────────┼────────────┼────────────┼────────────┼────────────┤"
+    );
+
+    let mut totals = [0.0; 4];
+
+    for day in records.chunk_by(|a, b| a.day == b.day) {
+        let first = &day[0];
+        if let Status::Error(_) = first.status {
+            continue;
+        }
+
+        let part = |n: u8| day.iter().find(|r| r.part == n);
+        // Parts that weren't selected are left blank, failures are called out
+        let answer = |n: u8| match part(n) {
+            Some(Record {
+                answer: Some(answer),
+                ..
+            }) => answer.to_string(),
+            Some(r) => r.status.name().to_uppercase(),
+            None => "-".to_string(),
+        };
+        let time = |n: u8| part(n).and_then(|r| r.internal_ms);
+
+        let parse_ms = first.parse_ms.unwrap_or(0.0);
+        let part_ms = [time(1), time(2)];
+        let total_ms = parse_ms + part_ms.iter().flatten().sum::<f64>();
+        for (total, ms) in totals.iter_mut().zip([
+            parse_ms,
+            part_ms[0].unwrap_or(0.0),
+            part_ms[1].unwrap_or(0.0),
+            total_ms,
+        ]) {
+            *total += ms;
+        }
+
+        let cell = |ms: Option<f64>| ms.map_or_else(|| "-".to_string(), |ms| format!("{ms:.3}"));
+        println!(
+            "│ {:>4} │ {:>16} │ {:>16} │ {:>10} │ {:>10} │ {:>10} │ {:>10} │",
+            first.day,
+            answer(1),
+            answer(2),
+            cell(first.parse_ms),
+            cell(part_ms[0]),
+            cell(part_ms[1]),
+            cell(first.parse_ms.map(|_| total_ms))
+        );
+    }
+
+    println!(
+        "├──────┼──────────────────┼──────────────────┼────────────┼────────────┼────────────┼────────────┤"
+    );
+    println!(
+        "│Total │ {:>16} │ {:>16} │ {:>10.3} │ {:>10.3} │ {:>10.3} │ {:>10.3} │",
+        "", "", totals[0], totals[1], totals[2], totals[3]
+    );
+    println!(
+        "└──────┴──────────────────┴──────────────────┴────────────┴────────────┴────────────┴────────────┘"
+    );
+}
+
+/// Format a byte count with a binary unit, e.g. `1.5 MiB`.
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Allocation counts per step of each day, from `--alloc-stats`, along with
+/// the peak RSS of the process that ran the day.
+pub fn print_memory_table(records: &[Record]) {
+    println!("\nMemory");
+    println!("┌──────┬───────┬─────────────┬─────────────┬─────────────┬─────────────┐");
+    println!("│ Day  │ Step  │ Allocations │ Allocated   │ Peak heap   │ Peak RSS    │");
+    println!("├──────┼───────┼─────────────┼─────────────┼─────────────┼─────────────┤");
+
+    let row = |day: String, step: &str, alloc: Option<AllocStats>, rss: String| {
+        let (count, allocated, peak) = match alloc {
+            Some(a) => (
+                a.allocations.to_string(),
+                human_bytes(a.allocated_bytes),
+                human_bytes(a.peak_bytes),
+            ),
+            None => ("-".to_string(), "-".to_string(), "-".to_string()),
+        };
+        println!(
+            "│ {day:>4} │ {step:>5} │ {count:>11} │ {allocated:>11} │ {peak:>11} │ {rss:>11} │"
+        );
+    };
+
+    for day in records.chunk_by(|a, b| a.day == b.day) {
+        let first = &day[0];
+        if first.status != Status::Ok {
+            continue;
+        }
+
+        let rss = first
+            .peak_rss_kb
+            .map_or_else(|| "-".to_string(), |kb| human_bytes(kb * 1024));
+        row(first.day.to_string(), "parse", first.parse_alloc, rss);
+        for r in day {
+            row(String::new(), &r.part.to_string(), r.alloc, String::new());
+        }
+    }
+
+    println!("└──────┴───────┴─────────────┴─────────────┴─────────────┴─────────────┘");
+}
+
+/// List the input lines each day skipped under `--lenient`. This goes to
+/// stderr so it stays out of JSON, CSV and report output.
+pub fn print_skipped(records: &[Record]) {
+    for day in records.chunk_by(|a, b| a.day == b.day) {
+        let skipped = &day[0].skipped;
+        if skipped.is_empty() {
+            continue;
+        }
+        eprintln!(
+            "Day {:2}: skipped {} input line(s) that couldn't be parsed",
+            day[0].day,
+            skipped.len()
+        );
+        for line in skipped {
+            eprintln!("  {line}");
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_number(value: Option<f64>) -> String {
+    value.map_or_else(|| "null".to_string(), |v| format!("{v:.6}"))
+}
+
+fn json_answer(answer: &Option<Answer>) -> String {
+    match answer {
+        Some(Answer::Int(n)) => n.to_string(),
+        Some(Answer::Text(s)) => json_string(s),
+        None => "null".to_string(),
+    }
+}
+
+fn json_alloc(alloc: Option<AllocStats>) -> String {
+    alloc.map_or_else(
+        || "null".to_string(),
+        |a| {
+            format!(
+                "{{\"allocations\": {}, \"allocated_bytes\": {}, \"peak_bytes\": {}}}",
+                a.allocations, a.allocated_bytes, a.peak_bytes
+            )
+        },
+    )
+}
+
+fn error_message(status: &Status) -> Option<&str> {
+    match status {
+        Status::Error(e) => Some(e),
+        _ => None,
+    }
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    let mut out = String::new();
+    out.push_str("{\n");
+    let _ = writeln!(out, "  \"schema_version\": {SCHEMA_VERSION},");
+    out.push_str("  \"results\": [");
+
+    for (i, r) in records.iter().enumerate() {
+        out.push_str(if i == 0 { "\n" } else { ",\n" });
+        let _ = write!(
+            out,
+            "    {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ms\": {}, \"internal_ms\": {}, \
+             \"external_ms\": {}, \"parse_alloc\": {}, \"alloc\": {}, \"peak_rss_kb\": {}, \
+             \"status\": {}, \"error\": {}}}",
+            r.day,
+            r.part,
+            json_answer(&r.answer),
+            json_number(r.parse_ms),
+            json_number(r.internal_ms),
+            json_number(r.external_ms),
+            json_alloc(r.parse_alloc),
+            json_alloc(r.alloc),
+            r.peak_rss_kb
+                .map_or_else(|| "null".to_string(), |kb| kb.to_string()),
+            json_string(r.status.name()),
+            error_message(&r.status).map_or_else(|| "null".to_string(), json_string),
+        );
+    }
+
+    out.push_str("\n  ]\n}");
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut out = String::from(
+        "schema_version,day,part,answer,parse_ms,internal_ms,external_ms,\
+         parse_allocations,parse_allocated_bytes,parse_peak_bytes,\
+         allocations,allocated_bytes,peak_bytes,peak_rss_kb,status,error\n",
+    );
+    let ms = |v: Option<f64>| v.map(|v| format!("{v:.6}")).unwrap_or_default();
+    let alloc = |a: Option<AllocStats>| match a {
+        Some(a) => format!("{},{},{}", a.allocations, a.allocated_bytes, a.peak_bytes),
+        None => ",,".to_string(),
+    };
+
+    for r in records {
+        let answer = r.answer.as_ref().map(Answer::to_string).unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            SCHEMA_VERSION,
+            r.day,
+            r.part,
+            csv_field(&answer),
+            ms(r.parse_ms),
+            ms(r.internal_ms),
+            ms(r.external_ms),
+            alloc(r.parse_alloc),
+            alloc(r.alloc),
+            r.peak_rss_kb.map(|kb| kb.to_string()).unwrap_or_default(),
+            r.status.name(),
+            csv_field(error_message(&r.status).unwrap_or_default()),
+        );
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 12,
+                part: 2,
+                answer: Some(Answer::Text("Merry, \"christmas\"".to_string())),
+                parse_ms: Some(0.125),
+                internal_ms: Some(0.5),
+                external_ms: Some(1.25),
+                alloc: Some(AllocStats {
+                    allocations: 7,
+                    allocated_bytes: 2048,
+                    peak_bytes: 512,
+                }),
+                peak_rss_kb: Some(1900),
+                ..Record::failed(12, 2, Status::Ok)
+            },
+            Record::failed(3, 1, Status::Error("missing input".to_string())),
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        let json = to_json(&records());
+        assert!(json.contains("\"schema_version\": 4,"));
+        assert!(json.contains(
+            "{\"day\": 12, \"part\": 2, \"answer\": \"Merry, \\\"christmas\\\"\", \
+             \"parse_ms\": 0.125000, \"internal_ms\": 0.500000, \"external_ms\": 1.250000, \
+             \"parse_alloc\": null, \
+             \"alloc\": {\"allocations\": 7, \"allocated_bytes\": 2048, \"peak_bytes\": 512}, \
+             \"peak_rss_kb\": 1900, \"status\": \"ok\", \"error\": null}"
+        ));
+        assert!(json.contains("\"answer\": null, \"parse_ms\": null, \"internal_ms\": null"));
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = to_csv(&records());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "4,12,2,\"Merry, \"\"christmas\"\"\",0.125000,0.500000,1.250000,,,,7,2048,512,1900,ok,"
+        );
+        assert_eq!(lines[2], "4,3,1,,,,,,,,,,,,error,missing input");
+    }
+
+    #[test]
+    fn test_human_bytes() {
+        assert_eq!(human_bytes(512), "512 B");
+        assert_eq!(human_bytes(1536), "1.5 KiB");
+        assert_eq!(human_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,132 @@
+use std::panic;
+use std::time::{Duration, Instant};
+
+use utils::Answer;
+use utils::Parts;
+use utils::alloc::AllocStats;
+
+use crate::registry::Entry;
+
+/// How a single day/part run ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Ok,
+    /// The day couldn't be run at all, e.g. its input is missing
+    Error(String),
+    Panicked,
+    /// Killed after running past the timeout
+    Timeout,
+    /// Ran out of memory, either the `--memory` cap or the whole machine
+    OutOfMemory,
+}
+
+impl Status {
+    /// Stable name used in the machine readable outputs
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Error(_) => "error",
+            Status::Panicked => "panic",
+            Status::Timeout => "timeout",
+            Status::OutOfMemory => "oom",
+        }
+    }
+}
+
+/// Outcome of running one part of one day.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    /// Time spent parsing the day's input, shared by both parts, in ms
+    pub parse_ms: Option<f64>,
+    /// Time spent solving this part, excluding parsing, in ms
+    pub internal_ms: Option<f64>,
+    /// Wall time for the whole day including reading the input, in ms
+    pub external_ms: Option<f64>,
+    /// Allocations while parsing, shared by both parts. Only counted with
+    /// `--alloc-stats`
+    pub parse_alloc: Option<AllocStats>,
+    /// Allocations while solving this part, excluding parsing
+    pub alloc: Option<AllocStats>,
+    /// Peak resident memory of the process that ran the whole day, in KiB
+    pub peak_rss_kb: Option<u64>,
+    /// Input lines the day skipped under `--lenient`, shared by both parts
+    pub skipped: Vec<String>,
+    pub status: Status,
+}
+
+impl Record {
+    pub fn failed(day: u8, part: u8, status: Status) -> Self {
+        Self {
+            day,
+            part,
+            answer: None,
+            parse_ms: None,
+            internal_ms: None,
+            external_ms: None,
+            parse_alloc: None,
+            alloc: None,
+            peak_rss_kb: None,
+            skipped: Vec::new(),
+            status,
+        }
+    }
+}
+
+/// Run the selected parts of a day once, producing a record per part.
+pub fn run_day(
+    entry: &Entry,
+    parts: Parts,
+    load_input: impl FnOnce(u8) -> Result<String, String>,
+) -> Vec<Record> {
+    let selected: Vec<u8> = [1, 2].into_iter().filter(|&p| parts.includes(p)).collect();
+    let fail_all = |status: Status| {
+        selected
+            .iter()
+            .map(|&part| Record::failed(entry.day, part, status.clone()))
+            .collect()
+    };
+
+    let start = Instant::now();
+
+    let input = match load_input(entry.day) {
+        Ok(input) => input,
+        Err(e) => return fail_all(Status::Error(e)),
+    };
+
+    // The parts share one parse, so a panic anywhere fails the whole day
+    let timings = match panic::catch_unwind(|| (entry.run)(&input, parts)) {
+        Ok(Ok(timings)) => timings,
+        Ok(Err(e)) => return fail_all(Status::Error(format!("Invalid input: {e}"))),
+        Err(_) => return fail_all(Status::Panicked),
+    };
+
+    let external_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+    selected
+        .iter()
+        .map(|&part| match timings.part(part) {
+            Some(result) => Record {
+                day: entry.day,
+                part,
+                answer: Some(result.answer.clone()),
+                parse_ms: Some(ms(timings.parse)),
+                internal_ms: Some(ms(result.time)),
+                external_ms: Some(external_ms),
+                parse_alloc: timings.parse_alloc,
+                alloc: result.alloc,
+                peak_rss_kb: None,
+                skipped: timings.skipped.iter().map(ToString::to_string).collect(),
+                status: Status::Ok,
+            },
+            None => Record::failed(
+                entry.day,
+                part,
+                Status::Error("part not reported".to_string()),
+            ),
+        })
+        .collect()
+}
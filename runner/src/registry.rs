@@ -0,0 +1,36 @@
+use utils::{Error, Parts, Solution, Timings};
+
+/// A day's solution with its types erased, so every day fits in one table.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub day: u8,
+    /// Parse the input and solve the requested parts, see [`utils::solve`]
+    pub run: fn(&str, Parts) -> Result<Timings, Error>,
+}
+
+impl Entry {
+    fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            run: utils::solve::<S>,
+        }
+    }
+}
+
+/// Every day the runner knows how to call, in day order.
+pub fn days() -> Vec<Entry> {
+    vec![
+        Entry::of::<day1::Day1>(),
+        Entry::of::<day2::Day2>(),
+        Entry::of::<day3::Day3>(),
+        Entry::of::<day4::Day4>(),
+        Entry::of::<day5::Day5>(),
+        Entry::of::<day6::Day6>(),
+        Entry::of::<day7::Day7>(),
+        Entry::of::<day8::Day8>(),
+        Entry::of::<day9::Day9>(),
+        Entry::of::<day10::Day10>(),
+        Entry::of::<day11::Day11>(),
+        Entry::of::<day12::Day12>(),
+    ]
+}
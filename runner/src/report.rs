@@ -0,0 +1,314 @@
+//! `runner report`: the results of one run rendered as Markdown or HTML,
+//! for pasting into the wiki.
+
+use std::fmt::Write;
+
+use crate::baseline::{self, Snapshot};
+use crate::record::{Record, Status};
+
+/// Longest bar in the Markdown chart, in characters.
+const MD_BAR_WIDTH: usize = 40;
+/// Size of the HTML chart, in pixels.
+const SVG_WIDTH: usize = 640;
+const SVG_BAR_HEIGHT: usize = 18;
+const SVG_LABEL_WIDTH: usize = 60;
+
+pub struct ReportOptions<'a> {
+    pub redact: bool,
+    /// Baseline name and its medians, to show the change in each part's time
+    pub baseline: Option<(&'a str, &'a Snapshot)>,
+}
+
+/// One day's worth of records, flattened for rendering.
+struct Row {
+    day: u8,
+    answers: [String; 2],
+    parse_ms: Option<f64>,
+    part_ms: [Option<f64>; 2],
+    total_ms: f64,
+    /// Change against the baseline median in percent, per part
+    delta: [Option<f64>; 2],
+}
+
+fn rows(records: &[Record], options: &ReportOptions) -> Vec<Row> {
+    records
+        .chunk_by(|a, b| a.day == b.day)
+        .filter(|day| !matches!(day[0].status, Status::Error(_)))
+        .map(|day| {
+            let first = &day[0];
+            let part = |n: u8| day.iter().find(|r| r.part == n);
+
+            let answer = |n: u8| match part(n) {
+                None => "-".to_string(),
+                Some(r) if r.status != Status::Ok => r.status.name().to_uppercase(),
+                Some(_) if options.redact => "(redacted)".to_string(),
+                Some(r) => r
+                    .answer
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            };
+            let time = |n: u8| part(n).and_then(|r| r.internal_ms);
+            let delta = |n: u8| {
+                let (_, snapshot) = options.baseline?;
+                let before = snapshot.get(&(first.day, n))?;
+                Some(baseline::delta_percent(before.median, time(n)?))
+            };
+
+            let part_ms = [time(1), time(2)];
+            Row {
+                day: first.day,
+                answers: [answer(1), answer(2)],
+                parse_ms: first.parse_ms,
+                part_ms,
+                total_ms: first.parse_ms.unwrap_or(0.0) + part_ms.iter().flatten().sum::<f64>(),
+                delta: [delta(1), delta(2)],
+            }
+        })
+        .collect()
+}
+
+fn errors(records: &[Record]) -> Vec<(u8, &str)> {
+    records
+        .chunk_by(|a, b| a.day == b.day)
+        .filter_map(|day| match &day[0].status {
+            Status::Error(e) => Some((day[0].day, e.as_str())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn ms_cell(ms: Option<f64>) -> String {
+    ms.map_or_else(|| "-".to_string(), |ms| format!("{ms:.3}"))
+}
+
+fn time_cell(ms: Option<f64>, delta: Option<f64>) -> String {
+    match delta {
+        Some(delta) => format!("{} ({delta:+.1}%)", ms_cell(ms)),
+        None => ms_cell(ms),
+    }
+}
+
+fn max_total(rows: &[Row]) -> f64 {
+    rows.iter().map(|r| r.total_ms).fold(0.0, f64::max)
+}
+
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+pub fn markdown(records: &[Record], options: &ReportOptions) -> String {
+    let rows = rows(records, options);
+    let mut out = String::from("# Advent of Code 2025 - Results\n\n");
+
+    if let Some((name, _)) = options.baseline {
+        let _ = writeln!(out, "Part times compared to baseline `{name}`.\n");
+    }
+
+    out.push_str("| Day | Part 1 | Part 2 | Parse ms | Part 1 ms | Part 2 ms | Total ms |\n");
+    out.push_str("|----:|-------:|-------:|---------:|----------:|----------:|---------:|\n");
+    for row in &rows {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} | {:.3} |",
+            row.day,
+            md_cell(&row.answers[0]),
+            md_cell(&row.answers[1]),
+            ms_cell(row.parse_ms),
+            time_cell(row.part_ms[0], row.delta[0]),
+            time_cell(row.part_ms[1], row.delta[1]),
+            row.total_ms
+        );
+    }
+    let total: f64 = rows.iter().map(|r| r.total_ms).sum();
+    let _ = writeln!(out, "| **Total** | | | | | | **{total:.3}** |");
+
+    let errors = errors(records);
+    if !errors.is_empty() {
+        out.push_str("\n## Errors\n\n");
+        for (day, e) in errors {
+            let _ = writeln!(out, "- Day {day}: {e}");
+        }
+    }
+
+    out.push_str("\n## Relative cost\n\n```text\n");
+    let max = max_total(&rows);
+    for row in &rows {
+        let width = if max > 0.0 {
+            (row.total_ms / max * MD_BAR_WIDTH as f64).round() as usize
+        } else {
+            0
+        };
+        let _ = writeln!(
+            out,
+            "Day {:>2} {:<bar$} {:.3} ms",
+            row.day,
+            "█".repeat(width),
+            row.total_ms,
+            bar = MD_BAR_WIDTH
+        );
+    }
+    out.push_str("```\n");
+
+    out
+}
+
+fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Horizontal bar per day, scaled to the most expensive one.
+fn svg_chart(rows: &[Row]) -> String {
+    let max = max_total(rows);
+    let bar_space = (SVG_WIDTH - SVG_LABEL_WIDTH - 100) as f64;
+    let height = rows.len() * (SVG_BAR_HEIGHT + 4) + 4;
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{SVG_WIDTH}\" height=\"{height}\" \
+         font-family=\"sans-serif\" font-size=\"12\">\n"
+    );
+    for (i, row) in rows.iter().enumerate() {
+        let y = 4 + i * (SVG_BAR_HEIGHT + 4);
+        let width = if max > 0.0 {
+            row.total_ms / max * bar_space
+        } else {
+            0.0
+        };
+        let text_y = y + SVG_BAR_HEIGHT - 5;
+        let _ = writeln!(
+            out,
+            "  <text x=\"0\" y=\"{text_y}\">Day {}</text>\n  \
+             <rect x=\"{SVG_LABEL_WIDTH}\" y=\"{y}\" width=\"{width:.1}\" height=\"{SVG_BAR_HEIGHT}\" fill=\"#4a7ebb\"/>\n  \
+             <text x=\"{:.1}\" y=\"{text_y}\">{:.3} ms</text>",
+            row.day,
+            SVG_LABEL_WIDTH as f64 + width + 6.0,
+            row.total_ms
+        );
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+pub fn html(records: &[Record], options: &ReportOptions) -> String {
+    let rows = rows(records, options);
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code 2025 - Results</title>\n\
+         <style>\n\
+         table { border-collapse: collapse; }\n\
+         th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: right; }\n\
+         </style>\n</head>\n<body>\n<h1>Advent of Code 2025 - Results</h1>\n",
+    );
+
+    if let Some((name, _)) = options.baseline {
+        let _ = writeln!(
+            out,
+            "<p>Part times compared to baseline <code>{}</code>.</p>",
+            html_escape(name)
+        );
+    }
+
+    out.push_str(
+        "<table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Parse ms</th>\
+         <th>Part 1 ms</th><th>Part 2 ms</th><th>Total ms</th></tr>\n",
+    );
+    for row in &rows {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.3}</td></tr>",
+            row.day,
+            html_escape(&row.answers[0]),
+            html_escape(&row.answers[1]),
+            ms_cell(row.parse_ms),
+            time_cell(row.part_ms[0], row.delta[0]),
+            time_cell(row.part_ms[1], row.delta[1]),
+            row.total_ms
+        );
+    }
+    let total: f64 = rows.iter().map(|r| r.total_ms).sum();
+    let _ = writeln!(
+        out,
+        "<tr><th>Total</th><td></td><td></td><td></td><td></td><td></td><th>{total:.3}</th></tr>\n</table>"
+    );
+
+    let errors = errors(records);
+    if !errors.is_empty() {
+        out.push_str("<h2>Errors</h2>\n<ul>\n");
+        for (day, e) in errors {
+            let _ = writeln!(out, "<li>Day {day}: {}</li>", html_escape(e));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    out.push_str("<h2>Relative cost</h2>\n");
+    out.push_str(&svg_chart(&rows));
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::Stats;
+    use utils::Answer;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                answer: Some(Answer::Int(3)),
+                parse_ms: Some(1.0),
+                internal_ms: Some(2.0),
+                ..Record::failed(1, 1, Status::Ok)
+            },
+            Record {
+                answer: Some(Answer::Text("a<b|c".to_string())),
+                parse_ms: Some(1.0),
+                internal_ms: Some(5.0),
+                ..Record::failed(1, 2, Status::Ok)
+            },
+            Record::failed(2, 1, Status::Error("missing input".to_string())),
+            Record::failed(10, 1, Status::Timeout),
+        ]
+    }
+
+    fn stats(median: f64) -> Stats {
+        Stats::from_samples(&[median])
+    }
+
+    #[test]
+    fn test_markdown() {
+        let snapshot = Snapshot::from([((1, 1), stats(1.0))]);
+        let options = ReportOptions {
+            redact: false,
+            baseline: Some(("main", &snapshot)),
+        };
+        let md = markdown(&records(), &options);
+        assert!(md.contains("| 1 | 3 | a<b\\|c | 1.000 | 2.000 (+100.0%) | 5.000 | 8.000 |"));
+        assert!(md.contains("| 10 | TIMEOUT | - | - | - | - | 0.000 |"));
+        assert!(md.contains("- Day 2: missing input"));
+        assert!(md.contains(&format!("Day  1 {} 8.000 ms", "█".repeat(MD_BAR_WIDTH))));
+    }
+
+    #[test]
+    fn test_html_redacted() {
+        let options = ReportOptions {
+            redact: true,
+            baseline: None,
+        };
+        let html = html(&records(), &options);
+        assert!(html.contains("<td>(redacted)</td><td>(redacted)</td>"));
+        assert!(!html.contains("a<b"));
+        assert!(html.contains("<svg"));
+        assert!(html.contains("<td>TIMEOUT</td>"));
+    }
+}
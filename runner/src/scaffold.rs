@@ -0,0 +1,252 @@
+//! `runner new <day>`: create a day crate from `runner/templates` and wire
+//! it into the workspace, the registry and the answers file.
+
+use std::fs;
+use std::path::Path;
+
+use utils::input::workspace_root;
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.tmpl");
+const MAIN_TEMPLATE: &str = include_str!("../templates/main.rs.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.tmpl");
+
+fn render(template: &str, day: u8) -> String {
+    template.replace("{{DAY}}", &day.to_string())
+}
+
+/// Insert `new_line` among the lines that `day_of` recognises, keeping them
+/// in day order. Lines it doesn't recognise are left where they are.
+fn insert_line(
+    text: &str,
+    day: u8,
+    new_line: &str,
+    day_of: impl Fn(&str) -> Option<u8>,
+) -> Result<String, String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| day_of(line).map(|d| (i, d)))
+        .collect();
+
+    if days.iter().any(|&(_, d)| d == day) {
+        return Err(format!("day{day} is already listed"));
+    }
+
+    let at = match days.iter().rev().find(|&&(_, d)| d < day) {
+        Some(&(i, _)) => i + 1,
+        None => days
+            .first()
+            .map(|&(i, _)| i)
+            .ok_or("no existing days to insert next to")?,
+    };
+
+    let mut out: Vec<&str> = lines[..at].to_vec();
+    out.push(new_line);
+    out.extend(&lines[at..]);
+    Ok(out.join("\n") + "\n")
+}
+
+/// Pull the day number out of e.g. `day12` followed by anything that isn't
+/// a digit.
+fn day_after(line: &str, prefix: &str) -> Option<u8> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+fn add_workspace_member(text: &str, day: u8) -> Result<String, String> {
+    insert_line(text, day, &format!("    \"day{day}\","), |line| {
+        day_after(line, "\"day").filter(|_| line.trim_end().ends_with("\","))
+    })
+}
+
+fn add_runner_dependency(text: &str, day: u8) -> Result<String, String> {
+    insert_line(
+        text,
+        day,
+        &format!("day{day} = {{ path = \"../day{day}\" }}"),
+        |line| day_after(line, "day").filter(|_| line.contains("path = ")),
+    )
+}
+
+fn add_registry_entry(text: &str, day: u8) -> Result<String, String> {
+    insert_line(
+        text,
+        day,
+        &format!("        Entry::of::<day{day}::Day{day}>(),"),
+        |line| day_after(line, "Entry::of::<day"),
+    )
+}
+
+/// Add an empty `[dayN]` section before the first later day, or at the end.
+fn add_answers_section(text: &str, day: u8) -> Result<String, String> {
+    let header = |line: &str| day_after(line, "[day").filter(|_| line.trim_end().ends_with(']'));
+    if text.lines().any(|line| header(line) == Some(day)) {
+        return Err(format!("day{day} is already listed"));
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+    let section = format!("[day{day}]");
+    let mut out: Vec<&str> = Vec::new();
+    match lines
+        .iter()
+        .position(|line| header(line).is_some_and(|d| d > day))
+    {
+        Some(at) => {
+            out.extend(&lines[..at]);
+            out.extend([section.as_str(), ""]);
+            out.extend(&lines[at..]);
+        }
+        None => {
+            out.extend(&lines);
+            if out.last().is_some_and(|line| !line.is_empty()) {
+                out.push("");
+            }
+            out.push(&section);
+        }
+    }
+    Ok(out.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Create `dayN/` and register it everywhere a day needs to be listed.
+///
+/// Every edit is worked out before anything is written, so a day that's
+/// already partly wired in fails without touching any files.
+pub fn new_day(day: u8) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day must be between 1 and 25, got {day}"));
+    }
+
+    let root = workspace_root();
+    let crate_dir = root.join(format!("day{day}"));
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()));
+    }
+
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("runner/Cargo.toml");
+    let registry = root.join("runner/src/registry.rs");
+    let answers = root.join("answers.toml");
+
+    let with_path = |path: &Path, e: String| format!("{}: {}", path.display(), e);
+    let edits = [
+        (&workspace, add_workspace_member(&read(&workspace)?, day)),
+        (&runner, add_runner_dependency(&read(&runner)?, day)),
+        (&registry, add_registry_entry(&read(&registry)?, day)),
+        (&answers, add_answers_section(&read(&answers)?, day)),
+    ]
+    .map(|(path, edit)| {
+        edit.map(|text| (path, text))
+            .map_err(|e| with_path(path, e))
+    });
+    let edits = edits.into_iter().collect::<Result<Vec<_>, _>>()?;
+
+    let src = crate_dir.join("src");
+    fs::create_dir_all(&src).map_err(|e| format!("Failed to create {}: {}", src.display(), e))?;
+    write(&crate_dir.join("Cargo.toml"), &render(CARGO_TEMPLATE, day))?;
+    write(&src.join("main.rs"), &render(MAIN_TEMPLATE, day))?;
+    write(&src.join("lib.rs"), &render(LIB_TEMPLATE, day))?;
+    println!("Created {}", crate_dir.display());
+
+    for (path, text) in edits {
+        write(path, &text)?;
+        println!("Updated {}", path.display());
+    }
+
+    println!("\nPut the puzzle input in inputs/day{day}.txt and run `cargo run -p day{day}`");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_workspace_member() {
+        let text = "[workspace]\nmembers = [\n    \"day1\",\n    \"day2\",\n    \"day10\",\n    \"utils\",\n]\n";
+        assert_eq!(
+            add_workspace_member(text, 3).unwrap(),
+            "[workspace]\nmembers = [\n    \"day1\",\n    \"day2\",\n    \"day3\",\n    \"day10\",\n    \"utils\",\n]\n"
+        );
+        assert_eq!(
+            add_workspace_member(text, 2).unwrap_err(),
+            "day2 is already listed"
+        );
+    }
+
+    #[test]
+    fn test_add_registry_entry() {
+        let text = "    vec![\n        Entry::of::<day1::Day1>(),\n        Entry::of::<day12::Day12>(),\n    ]\n";
+        assert_eq!(
+            add_registry_entry(text, 13).unwrap(),
+            "    vec![\n        Entry::of::<day1::Day1>(),\n        Entry::of::<day12::Day12>(),\n        Entry::of::<day13::Day13>(),\n    ]\n"
+        );
+    }
+
+    /// Render the templates into a throwaway crate and make sure it, and
+    /// its tests, still type-check against `utils`.
+    #[test]
+    fn test_templates_compile() {
+        let day = 25;
+        let dir = std::env::temp_dir().join(format!("runner-scaffold-{}", std::process::id()));
+        let src = dir.join("src");
+        fs::create_dir_all(&src).unwrap();
+
+        // The template's manifest inherits from the workspace, which the
+        // throwaway crate isn't part of
+        let utils = Path::new(env!("CARGO_MANIFEST_DIR")).join("../utils");
+        let manifest = format!(
+            "[package]\nname = \"day{day}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n\
+             [dependencies]\nutils = {{ path = {:?} }}\n\n[workspace]\n",
+            utils.canonicalize().unwrap()
+        );
+        assert!(render(CARGO_TEMPLATE, day).contains(&format!("name = \"day{day}\"")));
+        fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+        fs::write(src.join("main.rs"), render(MAIN_TEMPLATE, day)).unwrap();
+        fs::write(src.join("lib.rs"), render(LIB_TEMPLATE, day)).unwrap();
+
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let output = std::process::Command::new(cargo)
+            .args([
+                "check",
+                "--quiet",
+                "--offline",
+                "--all-targets",
+                "--manifest-path",
+            ])
+            .arg(dir.join("Cargo.toml"))
+            .env("CARGO_TARGET_DIR", dir.join("target"))
+            .output()
+            .unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(
+            output.status.success(),
+            "the day template doesn't compile:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn test_add_answers_section() {
+        let text = "# answers\n\n[day1]\npart1 = 3\n\n[day12]\n";
+        assert_eq!(
+            add_answers_section(text, 5).unwrap(),
+            "# answers\n\n[day1]\npart1 = 3\n\n[day5]\n\n[day12]\n"
+        );
+        assert_eq!(
+            add_answers_section(text, 13).unwrap(),
+            "# answers\n\n[day1]\npart1 = 3\n\n[day12]\n\n[day13]\n"
+        );
+    }
+}
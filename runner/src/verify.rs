@@ -0,0 +1,115 @@
+use crate::answers::Answers;
+use crate::record::{Record, Status};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    /// Parts with no expected answer on file
+    pub unchecked: usize,
+}
+
+impl Summary {
+    pub fn ok(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// Compare every record against its expected answer, printing a line per
+/// day/part and returning the totals.
+pub fn check(records: &[Record], answers: &Answers) -> Summary {
+    let mut summary = Summary::default();
+
+    for record in records {
+        let label = format!("Day {:2} part {}", record.day, record.part);
+        let expected = answers.get(record.day, record.part);
+
+        match (&record.status, &record.answer, expected) {
+            (Status::Ok, Some(got), Some(expected)) if got == expected => {
+                println!("{label}: ok");
+                summary.passed += 1;
+            }
+            (Status::Ok, Some(got), Some(expected)) => {
+                println!("{label}: MISMATCH - got {got}, expected {expected}");
+                summary.failed += 1;
+            }
+            (Status::Ok, None, Some(expected)) => {
+                println!("{label}: NO ANSWER - expected {expected}");
+                summary.failed += 1;
+            }
+            (Status::Ok, _, None) => {
+                println!("{label}: no expected answer");
+                summary.unchecked += 1;
+            }
+            // A day that didn't finish is a failure whether or not its
+            // answer is known
+            (status, _, expected) => {
+                let reason = match status {
+                    Status::Error(e) => format!("ERROR - {e}"),
+                    _ => status.name().to_uppercase(),
+                };
+                match expected {
+                    Some(expected) => println!("{label}: {reason} (expected {expected})"),
+                    None => println!("{label}: {reason}"),
+                }
+                summary.failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} unchecked",
+        summary.passed, summary.failed, summary.unchecked
+    );
+    if summary.unchecked > 0 {
+        eprintln!(
+            "warning: {} part(s) have no expected answer in answers.toml, so verify can't \
+             catch regressions in them",
+            summary.unchecked
+        );
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::Answer;
+
+    fn record(day: u8, part: u8, answer: i64) -> Record {
+        Record {
+            day,
+            part,
+            answer: Some(Answer::Int(answer)),
+            parse_ms: Some(0.5),
+            internal_ms: Some(1.0),
+            external_ms: Some(1.0),
+            ..Record::failed(day, part, Status::Ok)
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("[day1]\npart1 = 3\npart2 = 6\n[day2]\npart1 = 5").unwrap();
+        let records = vec![
+            record(1, 1, 3),
+            record(1, 2, 7),
+            Record::failed(2, 1, Status::Panicked),
+            record(2, 2, 1),
+            Record::failed(3, 1, Status::Error("no input".to_string())),
+            Record::failed(3, 2, Status::Timeout),
+        ];
+
+        let summary = check(&records, &answers);
+        assert_eq!(
+            summary,
+            Summary {
+                passed: 1,
+                failed: 4,
+                unchecked: 1
+            }
+        );
+        assert!(!summary.ok());
+    }
+}
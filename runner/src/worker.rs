@@ -0,0 +1,530 @@
+//! Days run in a child copy of the runner so a day that hangs or eats all
+//! memory can be killed without taking the whole run down with it.
+//!
+//! The parent starts `runner --worker <DAY> ...`, the worker runs the day
+//! in-process and writes one line per result to stdout:
+//!
+//! ```text
+//! ok <part> <parse_ms> <internal_ms> <external_ms> <parse_alloc> <alloc> i:<integer> | t:<text>
+//! panic <part>
+//! error <part> <message>
+//! rss <peak_kb>
+//! skipped <message>
+//! bench <part> <min> <median> <mean> <p95> <stddev> <outliers>
+//! bench-error <message>
+//! ```
+//!
+//! Allocation counts are written as `<allocations>/<bytes>/<peak_bytes>`, and
+//! any missing value as `-`.
+
+use std::io::Read;
+use std::process::{Command, ExitCode, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use utils::alloc::{self, AllocStats};
+use utils::policy::{self, Policy};
+use utils::{Answer, Parts};
+
+use crate::bench::{self, Stats};
+use crate::cli::{Mode, Options};
+use crate::record::{self, Record, Status};
+use crate::registry::Entry;
+
+/// How often the parent checks whether a worker has finished.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            },
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn encode_alloc(alloc: Option<AllocStats>) -> String {
+    alloc.map_or_else(
+        || "-".to_string(),
+        |a| format!("{}/{}/{}", a.allocations, a.allocated_bytes, a.peak_bytes),
+    )
+}
+
+fn decode_alloc(s: &str) -> Result<Option<AllocStats>, ()> {
+    if s == "-" {
+        return Ok(None);
+    }
+    let mut fields = s.split('/').map(|f| f.parse::<u64>().map_err(|_| ()));
+    let (Some(allocations), Some(allocated_bytes), Some(peak_bytes), None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err(());
+    };
+    Ok(Some(AllocStats {
+        allocations: allocations?,
+        allocated_bytes: allocated_bytes?,
+        peak_bytes: peak_bytes?,
+    }))
+}
+
+fn encode_records(records: &[Record], peak_rss_kb: Option<u64>) -> String {
+    let ms = |v: Option<f64>| v.map_or_else(|| "-".to_string(), |v| v.to_string());
+    let mut out = String::new();
+
+    for r in records {
+        let line = match (&r.status, &r.answer) {
+            (Status::Ok, Some(answer)) => {
+                let answer = match answer {
+                    Answer::Int(n) => format!("i:{n}"),
+                    Answer::Text(s) => format!("t:{}", escape(s)),
+                };
+                format!(
+                    "ok {} {} {} {} {} {} {}",
+                    r.part,
+                    ms(r.parse_ms),
+                    ms(r.internal_ms),
+                    ms(r.external_ms),
+                    encode_alloc(r.parse_alloc),
+                    encode_alloc(r.alloc),
+                    answer
+                )
+            }
+            (Status::Error(e), _) => format!("error {} {}", r.part, escape(e)),
+            _ => format!("panic {}", r.part),
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    if let Some(kb) = peak_rss_kb {
+        out.push_str(&format!("rss {kb}\n"));
+    }
+    // Skipped lines are per day, so only the first record's are sent
+    for line in records.first().map_or(&[][..], |r| &r.skipped) {
+        out.push_str(&format!("skipped {}\n", escape(line)));
+    }
+
+    out
+}
+
+fn decode_records(day: u8, text: &str) -> Result<Vec<Record>, String> {
+    let bad = |line: &str| format!("malformed worker output '{line}'");
+    let ms = |s: &str| match s {
+        "-" => Ok(None),
+        s => s.parse().map(Some),
+    };
+
+    let mut peak_rss_kb = None;
+    let mut skipped = Vec::new();
+    let mut records = text
+        .lines()
+        .filter(|line| {
+            if let Some(kb) = line.strip_prefix("rss ") {
+                peak_rss_kb = kb.parse().ok();
+            } else if let Some(message) = line.strip_prefix("skipped ") {
+                skipped.push(unescape(message));
+            } else {
+                return true;
+            }
+            false
+        })
+        .map(|line| {
+            let mut fields = line.splitn(8, ' ');
+            let kind = fields.next().unwrap_or_default();
+            let part: u8 = fields
+                .next()
+                .and_then(|p| p.parse().ok())
+                .ok_or_else(|| bad(line))?;
+
+            match kind {
+                "ok" => {
+                    let [
+                        Some(parse),
+                        Some(internal),
+                        Some(external),
+                        Some(parse_alloc),
+                        Some(alloc),
+                        Some(answer),
+                    ] = [(); 6].map(|_| fields.next())
+                    else {
+                        return Err(bad(line));
+                    };
+                    let answer = if let Some(n) = answer.strip_prefix("i:") {
+                        Answer::Int(n.parse().map_err(|_| bad(line))?)
+                    } else if let Some(s) = answer.strip_prefix("t:") {
+                        Answer::Text(unescape(s))
+                    } else {
+                        return Err(bad(line));
+                    };
+                    Ok(Record {
+                        day,
+                        part,
+                        answer: Some(answer),
+                        parse_ms: ms(parse).map_err(|_| bad(line))?,
+                        internal_ms: ms(internal).map_err(|_| bad(line))?,
+                        external_ms: ms(external).map_err(|_| bad(line))?,
+                        parse_alloc: decode_alloc(parse_alloc).map_err(|_| bad(line))?,
+                        alloc: decode_alloc(alloc).map_err(|_| bad(line))?,
+                        peak_rss_kb: None,
+                        skipped: Vec::new(),
+                        status: Status::Ok,
+                    })
+                }
+                "panic" => Ok(Record::failed(day, part, Status::Panicked)),
+                "error" => {
+                    let message = line.splitn(3, ' ').nth(2).unwrap_or_default();
+                    Ok(Record::failed(day, part, Status::Error(unescape(message))))
+                }
+                _ => Err(bad(line)),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for record in &mut records {
+        record.peak_rss_kb = peak_rss_kb;
+        record.skipped = skipped.clone();
+    }
+    Ok(records)
+}
+
+fn encode_stats(stats: &Result<Vec<(u8, Stats)>, String>) -> String {
+    match stats {
+        Ok(stats) => stats
+            .iter()
+            .map(|(part, s)| {
+                format!(
+                    "bench {} {} {} {} {} {} {}\n",
+                    part, s.min, s.median, s.mean, s.p95, s.stddev, s.outliers
+                )
+            })
+            .collect(),
+        Err(e) => format!("bench-error {}\n", escape(e)),
+    }
+}
+
+fn decode_stats(text: &str) -> Result<Vec<(u8, Stats)>, String> {
+    text.lines()
+        .map(|line| {
+            if let Some(message) = line.strip_prefix("bench-error ") {
+                return Err(unescape(message));
+            }
+            let bad = || format!("malformed worker output '{line}'");
+            let fields: Vec<&str> = line.split(' ').collect();
+            let ["bench", part, min, median, mean, p95, stddev, outliers] = fields[..] else {
+                return Err(bad());
+            };
+            let num = |s: &str| s.parse::<f64>().map_err(|_| bad());
+            let stats = Stats {
+                min: num(min)?,
+                median: num(median)?,
+                mean: num(mean)?,
+                p95: num(p95)?,
+                stddev: num(stddev)?,
+                outliers: outliers.parse().map_err(|_| bad())?,
+            };
+            Ok((part.parse().map_err(|_| bad())?, stats))
+        })
+        .collect()
+}
+
+/// Entry point when the runner is started with `--worker`: run one day and
+/// report back on stdout.
+pub fn serve(entry: &Entry, options: &Options) -> ExitCode {
+    if let Some(mb) = options.memory_mb
+        && let Err(e) = crate::limits::limit_memory(mb)
+    {
+        eprintln!("error: {e}");
+        return ExitCode::FAILURE;
+    }
+
+    if options.alloc_stats {
+        alloc::enable();
+    }
+    policy::set(options.policy);
+
+    let output = match options.mode {
+        Mode::Bench => encode_stats(&crate::load_input(entry.day).and_then(|input| {
+            bench::bench_day(
+                entry,
+                &input,
+                options.parts,
+                options.warmup,
+                options.iterations,
+            )
+        })),
+        _ => {
+            let records = record::run_day(entry, options.parts, crate::load_input);
+            encode_records(&records, alloc::peak_rss_kb())
+        }
+    };
+
+    print!("{output}");
+    ExitCode::SUCCESS
+}
+
+/// How a worker process ended.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Exited normally, with what it wrote to stdout
+    Finished(String),
+    TimedOut,
+    OutOfMemory,
+    /// Died some other way, e.g. a bad `--memory` value
+    Crashed(String),
+}
+
+fn worker_args(day: u8, options: &Options) -> Vec<String> {
+    let parts = match options.parts {
+        Parts::One => "1",
+        Parts::Two => "2",
+        Parts::Both => "both",
+    };
+    let mut args = vec![
+        "--worker".to_string(),
+        day.to_string(),
+        format!("--part={parts}"),
+    ];
+    if let Some(mb) = options.memory_mb {
+        args.push(format!("--memory={mb}"));
+    }
+    if options.alloc_stats {
+        args.push("--alloc-stats".to_string());
+    }
+    if options.policy == Policy::Lenient {
+        args.push("--lenient".to_string());
+    }
+    if options.mode == Mode::Bench {
+        args.push("bench".to_string());
+        args.push(format!("--warmup={}", options.warmup));
+        args.push(format!("--iterations={}", options.iterations));
+    }
+    args
+}
+
+fn drain(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut out = Vec::new();
+        let _ = pipe.read_to_end(&mut out);
+        String::from_utf8_lossy(&out).into_owned()
+    })
+}
+
+/// Allocation failures abort the process with this message on stderr.
+fn ran_out_of_memory(stderr: &str) -> bool {
+    stderr.contains("memory allocation of")
+}
+
+/// The kernel's OOM killer sends SIGKILL. So can anyone else, so it only
+/// counts as running out of memory when the worker had a memory cap. The
+/// runner's own kill on a timeout never gets here.
+#[cfg(unix)]
+fn killed_by_oom_killer(status: &ExitStatus, memory_capped: bool) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    memory_capped && status.signal() == Some(9)
+}
+
+#[cfg(not(unix))]
+fn killed_by_oom_killer(_status: &ExitStatus, _memory_capped: bool) -> bool {
+    false
+}
+
+/// How a worker ended, given its exit status, or `None` if the runner killed
+/// it for running out of time, and what it wrote.
+fn classify(
+    status: Option<ExitStatus>,
+    stdout: String,
+    stderr: &str,
+    memory_capped: bool,
+) -> Outcome {
+    let Some(status) = status else {
+        return Outcome::TimedOut;
+    };
+    if status.success() {
+        // Pass panic messages and the like through
+        eprint!("{stderr}");
+        return Outcome::Finished(stdout);
+    }
+    if ran_out_of_memory(stderr) || killed_by_oom_killer(&status, memory_capped) {
+        return Outcome::OutOfMemory;
+    }
+
+    match stderr.lines().find(|line| !line.trim().is_empty()) {
+        Some(line) => Outcome::Crashed(line.trim_start_matches("error: ").to_string()),
+        None => Outcome::Crashed(format!("worker exited with {status}")),
+    }
+}
+
+/// Run one day in a worker process, killing it once `options.timeout` has
+/// passed.
+pub fn spawn(day: u8, options: &Options) -> Outcome {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => return Outcome::Crashed(format!("can't find the runner executable: {e}")),
+    };
+
+    let mut child = match Command::new(exe)
+        .args(worker_args(day, options))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return Outcome::Crashed(format!("failed to start worker: {e}")),
+    };
+
+    // Read both pipes as the worker runs so it never blocks on a full pipe
+    let stdout = drain(child.stdout.take().expect("stdout is piped"));
+    let stderr = drain(child.stderr.take().expect("stderr is piped"));
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if options.timeout.is_some_and(|t| start.elapsed() >= t) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => return Outcome::Crashed(format!("failed to wait for worker: {e}")),
+        }
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    classify(status, stdout, &stderr, options.memory_mb.is_some())
+}
+
+/// Run the selected parts of a day in a worker, turning timeouts and crashes
+/// into records.
+pub fn run_day(day: u8, options: &Options) -> Vec<Record> {
+    let fail_all = |status: Status| {
+        [1, 2]
+            .into_iter()
+            .filter(|&part| options.parts.includes(part))
+            .map(|part| Record::failed(day, part, status.clone()))
+            .collect()
+    };
+
+    match spawn(day, options) {
+        Outcome::Finished(stdout) => {
+            decode_records(day, &stdout).unwrap_or_else(|e| fail_all(Status::Error(e)))
+        }
+        Outcome::TimedOut => fail_all(Status::Timeout),
+        Outcome::OutOfMemory => fail_all(Status::OutOfMemory),
+        Outcome::Crashed(e) => fail_all(Status::Error(e)),
+    }
+}
+
+/// Benchmark a day in a worker. The error is ready to print next to the day.
+pub fn bench_day(day: u8, options: &Options) -> Result<Vec<(u8, Stats)>, String> {
+    match spawn(day, options) {
+        Outcome::Finished(stdout) => decode_stats(&stdout).map_err(|e| format!("ERROR - {e}")),
+        Outcome::TimedOut => Err("TIMEOUT".to_string()),
+        Outcome::OutOfMemory => Err("OOM".to_string()),
+        Outcome::Crashed(e) => Err(format!("ERROR - {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_round_trip() {
+        let records = vec![
+            Record {
+                day: 12,
+                part: 1,
+                answer: Some(Answer::Int(-42)),
+                parse_ms: Some(0.25),
+                internal_ms: Some(1.5),
+                external_ms: Some(2.0),
+                parse_alloc: Some(AllocStats {
+                    allocations: 3,
+                    allocated_bytes: 4096,
+                    peak_bytes: 1024,
+                }),
+                alloc: None,
+                skipped: vec!["line 3, column 1: expected a digit (found 'x')".to_string()],
+                ..Record::failed(12, 1, Status::Ok)
+            },
+            Record {
+                day: 12,
+                part: 2,
+                answer: Some(Answer::Text("Merry \\ christmas\nand more".to_string())),
+                parse_ms: Some(0.25),
+                internal_ms: Some(0.0),
+                ..Record::failed(12, 2, Status::Ok)
+            },
+            Record::failed(12, 1, Status::Panicked),
+            Record::failed(12, 2, Status::Error("missing input".to_string())),
+        ];
+
+        let decoded = decode_records(12, &encode_records(&records, Some(2048))).unwrap();
+        assert_eq!(decoded.len(), records.len());
+        for (a, b) in decoded.iter().zip(&records) {
+            assert_eq!(a.part, b.part);
+            assert_eq!(a.answer, b.answer);
+            assert_eq!(a.internal_ms, b.internal_ms);
+            assert_eq!(a.external_ms, b.external_ms);
+            assert_eq!(a.parse_alloc, b.parse_alloc);
+            assert_eq!(a.alloc, b.alloc);
+            assert_eq!(a.peak_rss_kb, Some(2048));
+            assert_eq!(a.skipped, records[0].skipped);
+            assert_eq!(a.status, b.status);
+        }
+    }
+
+    #[test]
+    fn test_stats_round_trip() {
+        let stats = Stats::from_samples(&[1.0, 2.5, 3.0]);
+        let decoded = decode_stats(&encode_stats(&Ok(vec![(2, stats.clone())]))).unwrap();
+        assert_eq!(decoded, vec![(2, stats)]);
+
+        let err = decode_stats(&encode_stats(&Err("day1 panicked".to_string())));
+        assert_eq!(err, Err("day1 panicked".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_classify() {
+        use std::os::unix::process::ExitStatusExt;
+
+        // Raw wait statuses: a signal number, or an exit code shifted up a byte
+        let sigkill = Some(ExitStatus::from_raw(9));
+        let exit_1 = Some(ExitStatus::from_raw(1 << 8));
+        let classify = |status, stderr, capped| classify(status, String::new(), stderr, capped);
+
+        assert_eq!(classify(None, "", true), Outcome::TimedOut);
+        assert_eq!(
+            classify(Some(ExitStatus::from_raw(0)), "", false),
+            Outcome::Finished(String::new())
+        );
+        assert_eq!(classify(sigkill, "", true), Outcome::OutOfMemory);
+        assert_eq!(
+            classify(sigkill, "", false),
+            Outcome::Crashed("worker exited with signal: 9 (SIGKILL)".to_string())
+        );
+        assert_eq!(
+            classify(exit_1, "memory allocation of 8 bytes failed", false),
+            Outcome::OutOfMemory
+        );
+        assert_eq!(
+            classify(exit_1, "error: bad --memory value\n", false),
+            Outcome::Crashed("bad --memory value".to_string())
+        );
+    }
+}
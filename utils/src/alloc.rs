@@ -0,0 +1,172 @@
+//! Opt-in allocation tracking.
+//!
+//! A binary that wants numbers installs the counting allocator and switches
+//! it on:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOC: utils::alloc::CountingAlloc = utils::alloc::CountingAlloc;
+//!
+//! utils::alloc::enable();
+//! ```
+//!
+//! Until [`enable`] is called it forwards straight to the system allocator,
+//! so leaving it installed costs one atomic load per call. The counters are
+//! process wide, so measurements are only meaningful while one thing runs at
+//! a time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// Global allocator that counts calls and bytes on top of [`System`].
+pub struct CountingAlloc;
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    // Memory allocated before counting was enabled can be freed afterwards
+    let _ = LIVE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| {
+        Some(live.saturating_sub(size as u64))
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        if ENABLED.load(Ordering::Relaxed) {
+            record_dealloc(layout.size());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new
+    }
+}
+
+/// Start counting. Only has an effect if [`CountingAlloc`] is the global
+/// allocator.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Allocation activity over one measured step.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, counting each reallocation as one
+    pub allocations: u64,
+    /// Total bytes requested
+    pub allocated_bytes: u64,
+    /// Most bytes live at once, over what was already live at the start
+    pub peak_bytes: u64,
+}
+
+/// Run `f`, returning its result along with the allocations it made, or
+/// `None` if counting isn't enabled.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (result, Some(stats))
+}
+
+/// Peak resident set size of the current process in KiB, from the `VmHWM`
+/// line of `/proc/self/status`. `None` where `/proc` isn't available.
+pub fn peak_rss_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    // Other tests allocate on their own threads while these run, so only
+    // check for allocations much bigger than theirs
+    const BIG: usize = 64 << 20;
+
+    #[test]
+    fn test_measure() {
+        enable();
+        let (len, stats) = measure(|| Vec::<u8>::with_capacity(BIG).capacity());
+        assert_eq!(len, BIG);
+        let stats = stats.unwrap();
+        assert!(stats.allocations >= 1);
+        assert!(stats.allocated_bytes >= BIG as u64);
+        assert!(stats.peak_bytes >= BIG as u64);
+
+        // The next measurement starts from zero again
+        let ((), stats) = measure(|| ());
+        let stats = stats.unwrap();
+        assert!(stats.allocated_bytes < BIG as u64);
+        assert!(stats.peak_bytes < BIG as u64);
+    }
+
+    #[test]
+    fn test_peak_rss() {
+        let peak = peak_rss_kb();
+        if cfg!(target_os = "linux") {
+            assert!(peak.is_some_and(|kb| kb > 0));
+        } else {
+            assert_eq!(peak, None);
+        }
+    }
+}
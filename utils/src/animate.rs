@@ -0,0 +1,392 @@
+//! Recording a grid as it changes, to watch a simulation play out.
+//!
+//! A day makes a [`Recorder`], calls [`Recorder::record`] after each step and
+//! [`Recorder::finish`] at the end. Nothing is kept unless the day binary was
+//! run with `--visualize`, which [`run`](crate::run) passes on through
+//! [`set`]. Then `finish` only queues the frames, and once the parts have
+//! been timed `run` calls [`flush`], which replays them in the terminal, or
+//! writes them to `<name>.gif` or `<name>/0001.ppm`, `<name>/0002.ppm`, ...
+//! in the current directory.
+//!
+//! Like the parse [`policy`](crate::policy), the setting and the queue are
+//! kept per thread.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::Grid;
+use crate::render::{Render, Rgb};
+
+/// Where `--visualize` sends the frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Output {
+    #[default]
+    Terminal,
+    Gif,
+    Ppm,
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "terminal" => Ok(Output::Terminal),
+            "gif" => Ok(Output::Gif),
+            "ppm" => Ok(Output::Ppm),
+            _ => Err(format!(
+                "invalid --visualize value '{s}', expected terminal, gif or ppm"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Visualize {
+    pub output: Output,
+    /// Frames per second when replaying or in a GIF
+    pub fps: u32,
+}
+
+impl Default for Visualize {
+    fn default() -> Self {
+        Self {
+            output: Output::default(),
+            fps: 10,
+        }
+    }
+}
+
+type Export = Box<dyn FnOnce()>;
+
+thread_local! {
+    static VISUALIZE: RefCell<Option<Visualize>> = const { RefCell::new(None) };
+    static PENDING: RefCell<Vec<Export>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Record simulations on this thread from now on, or stop with `None`.
+pub fn set(visualize: Option<Visualize>) {
+    VISUALIZE.with(|v| *v.borrow_mut() = visualize);
+}
+
+pub fn current() -> Option<Visualize> {
+    VISUALIZE.with(|v| *v.borrow())
+}
+
+/// Show or save everything [`Recorder::finish`] has queued on this thread,
+/// oldest first.
+pub fn flush() {
+    let pending = PENDING.with(|p| p.take());
+    for export in pending {
+        export();
+    }
+}
+
+/// Snapshots of a grid, one per step of a simulation
+#[derive(Debug, Clone)]
+pub struct Recorder<T> {
+    name: String,
+    settings: Option<Visualize>,
+    frames: Vec<Grid<T>>,
+    every: usize,
+    steps: usize,
+    /// The latest step, if `every` skipped it
+    pending: Option<Grid<T>>,
+}
+
+impl<T: Clone> Recorder<T> {
+    /// A recorder for the current [`Visualize`] setting, which does nothing
+    /// if there isn't one. `name` names the files it writes.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self::with_settings(name, current())
+    }
+
+    pub fn with_settings(name: impl Into<String>, settings: Option<Visualize>) -> Self {
+        Self {
+            name: name.into(),
+            settings,
+            frames: Vec::new(),
+            every: 1,
+            steps: 0,
+            pending: None,
+        }
+    }
+
+    /// Only keep every `n`th step, for simulations with too many to watch.
+    /// The last step is always kept.
+    pub fn every(mut self, n: usize) -> Self {
+        assert!(n > 0, "can't keep every 0th step");
+        self.every = n;
+        self
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.settings.is_some()
+    }
+
+    /// Take a snapshot of `grid`, if recording
+    pub fn record(&mut self, grid: &Grid<T>) {
+        if !self.is_recording() {
+            return;
+        }
+        if self.steps.is_multiple_of(self.every) {
+            self.frames.push(grid.clone());
+            self.pending = None;
+        } else {
+            self.pending = Some(grid.clone());
+        }
+        self.steps += 1;
+    }
+
+    /// Every frame kept so far, oldest first
+    pub fn frames(&self) -> impl Iterator<Item = &Grid<T>> {
+        self.frames.iter().chain(&self.pending)
+    }
+
+    /// Write the frames as an animated GIF that loops forever. A GIF can
+    /// only have 256 colours, counting the background.
+    pub fn save_gif(&self, path: impl AsRef<Path>, fps: u32, render: &Render<T>) -> io::Result<()> {
+        let frames: Vec<_> = self.frames().map(|grid| render.pixels(grid)).collect();
+        std::fs::write(path, gif(&frames, fps)?)
+    }
+
+    /// Write each frame to `dir` as `0001.ppm`, `0002.ppm`, ..., creating
+    /// `dir` if need be.
+    pub fn save_ppm_sequence(&self, dir: impl AsRef<Path>, render: &Render<T>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        for (idx, grid) in self.frames().enumerate() {
+            std::fs::write(dir.join(format!("{:04}.ppm", idx + 1)), render.ppm(grid))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Clone + Display> Recorder<T> {
+    /// Play the frames back in the terminal, clearing it between frames.
+    pub fn replay(&self, fps: u32, render: &Render<T>) {
+        let delay = Duration::from_secs(1) / fps.max(1);
+        let mut stdout = io::stdout().lock();
+        for grid in self.frames() {
+            let _ = write!(stdout, "\x1b[2J\x1b[H{}", render.ansi(grid));
+            let _ = stdout.flush();
+            thread::sleep(delay);
+        }
+    }
+
+    /// Show or save what was recorded the way `--visualize` asked, at the
+    /// next [`flush`], so it isn't timed along with the part. Failing to
+    /// write files is reported on stderr rather than failing the day.
+    pub fn finish(self, render: Render<'static, T>)
+    where
+        T: 'static,
+    {
+        if self.is_recording() {
+            PENDING.with(|p| p.borrow_mut().push(Box::new(move || self.export(&render))));
+        }
+    }
+
+    fn export(&self, render: &Render<T>) {
+        let Some(Visualize { output, fps }) = self.settings else {
+            return;
+        };
+        let frames = self.frames().count();
+        let result = match output {
+            Output::Terminal => {
+                self.replay(fps, render);
+                return;
+            }
+            Output::Gif => {
+                let path = format!("{}.gif", self.name);
+                self.save_gif(&path, fps, render).map(|()| path)
+            }
+            Output::Ppm => self
+                .save_ppm_sequence(&self.name, render)
+                .map(|()| format!("{}/", self.name)),
+        };
+        match result {
+            Ok(path) => eprintln!("wrote {frames} frame(s) to {path}"),
+            Err(e) => eprintln!("warning: couldn't save {}: {e}", self.name),
+        }
+    }
+}
+
+/// Literals between clear codes, so LZW codes never need more than 9 bits
+const LZW_RUN: usize = 250;
+
+/// Animated GIF of `frames`, each its width, height and pixels row by row
+fn gif(frames: &[(usize, usize, Vec<Rgb>)], fps: u32) -> io::Result<Vec<u8>> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+
+    let (width, height) = frames.first().map_or((0, 0), |&(w, h, _)| (w, h));
+    if frames.iter().any(|&(w, h, _)| (w, h) != (width, height)) {
+        return Err(invalid("every frame must be the same size".to_string()));
+    }
+    let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(invalid(format!("{width}x{height} is too big for a GIF")));
+    };
+
+    let mut palette: Vec<Rgb> = Vec::new();
+    let mut indices = Vec::with_capacity(frames.len());
+    for (_, _, pixels) in frames {
+        let mut frame = Vec::with_capacity(pixels.len());
+        for &pixel in pixels {
+            let idx = match palette.iter().position(|&c| c == pixel) {
+                Some(idx) => idx,
+                None => {
+                    palette.push(pixel);
+                    palette.len() - 1
+                }
+            };
+            if idx > u8::MAX as usize {
+                return Err(invalid(
+                    "a GIF can't have more than 256 colours".to_string(),
+                ));
+            }
+            frame.push(idx as u8);
+        }
+        indices.push(frame);
+    }
+
+    // The colour table holds a power of two colours, at least 2
+    let bits = palette.len().max(2).next_power_of_two().trailing_zeros() as u8;
+    palette.resize(1 << bits, Rgb::BLACK);
+
+    let mut out = b"GIF89a".to_vec();
+    out.extend(width.to_le_bytes());
+    out.extend(height.to_le_bytes());
+    out.extend([0x80 | ((bits - 1) << 4) | (bits - 1), 0, 0]);
+    out.extend(palette.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+
+    // Loop forever
+    out.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    let delay = (100 / fps.max(1)).clamp(1, u16::MAX as u32) as u16;
+    for frame in &indices {
+        out.extend([0x21, 0xf9, 4, 0]);
+        out.extend(delay.to_le_bytes());
+        out.extend([0, 0]);
+
+        out.push(0x2c);
+        out.extend([0, 0, 0, 0]);
+        out.extend(width.to_le_bytes());
+        out.extend(height.to_le_bytes());
+        out.push(0);
+
+        out.push(8);
+        for block in lzw_uncompressed(frame).chunks(u8::MAX as usize) {
+            out.push(block.len() as u8);
+            out.extend(block);
+        }
+        out.push(0);
+    }
+
+    out.push(0x3b);
+    Ok(out)
+}
+
+/// GIF image data with 8-bit codes, written as literals only. Clearing the
+/// table before it outgrows 9-bit codes means no compression but no
+/// dictionary either.
+fn lzw_uncompressed(indices: &[u8]) -> Vec<u8> {
+    const CLEAR: u16 = 256;
+    const END: u16 = 257;
+
+    let mut out = Vec::new();
+    let (mut acc, mut len) = (0_u32, 0);
+    let mut put = |code: u16, out: &mut Vec<u8>| {
+        acc |= (code as u32) << len;
+        len += 9;
+        while len >= 8 {
+            out.push(acc as u8);
+            acc >>= 8;
+            len -= 8;
+        }
+    };
+
+    for run in indices.chunks(LZW_RUN) {
+        put(CLEAR, &mut out);
+        for &idx in run {
+            put(idx as u16, &mut out);
+        }
+    }
+    if indices.is_empty() {
+        put(CLEAR, &mut out);
+    }
+    put(END, &mut out);
+    if len > 0 {
+        out.push(acc as u8);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_char_grid;
+
+    #[test]
+    fn test_recorder() {
+        let settings = Some(Visualize::default());
+        let mut grid = parse_char_grid("..").unwrap();
+
+        let mut off = Recorder::with_settings("off", None);
+        off.record(&grid);
+        assert_eq!(off.frames().count(), 0);
+
+        let mut recorder = Recorder::with_settings("on", settings).every(2);
+        for col in 0..2 {
+            grid[0][col] = '#';
+            recorder.record(&grid);
+        }
+        let frames: Vec<_> = recorder.frames().map(|g| g.row(0).to_vec()).collect();
+        assert_eq!(frames, vec![vec!['#', '.'], vec!['#', '#']]);
+
+        let render = Render::new().colours(|&c| (c == '#').then_some(Rgb::WHITE));
+        let frames: Vec<_> = recorder.frames().map(|g| render.pixels(g)).collect();
+        let gif = gif(&frames, 10).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x02\x00\x01\x00\x80\x00\x00"));
+        assert_eq!(&gif[13..19], &[255, 255, 255, 0, 0, 0]);
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+
+    #[test]
+    fn test_flush() {
+        let dir = std::env::temp_dir().join(format!("animate-flush-{}", std::process::id()));
+        let settings = Visualize {
+            output: Output::Ppm,
+            fps: 10,
+        };
+        let mut recorder = Recorder::with_settings(dir.to_str().unwrap(), Some(settings));
+        recorder.record(&parse_char_grid("#.").unwrap());
+        recorder.finish(Render::new());
+
+        // Nothing is written until the flush
+        assert!(!dir.exists());
+        flush();
+        let written = dir.join("0001.ppm").exists();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(written);
+    }
+
+    #[test]
+    fn test_lzw() {
+        // CLEAR, 1, 2, END as 9-bit codes, least significant bit first
+        assert_eq!(
+            lzw_uncompressed(&[1, 2]),
+            vec![0x00, 0x03, 0x08, 0x08, 0x08]
+        );
+        assert_eq!(lzw_uncompressed(&[0; 600]).len(), (3 + 600 + 1) * 9 / 8 + 1);
+    }
+
+    #[test]
+    fn test_output() {
+        assert_eq!("gif".parse(), Ok(Output::Gif));
+        assert!("mp4".parse::<Output>().is_err());
+    }
+}
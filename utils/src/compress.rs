@@ -0,0 +1,112 @@
+//! Coordinate compression: squeezing a handful of far apart values onto
+//! consecutive indices, so a grid only needs a cell per value that matters.
+//!
+//! A [`Compressor`] made with [`new`](Compressor::new) has one cell per
+//! distinct value. One made with [`with_gaps`](Compressor::with_gaps) also
+//! has a cell for each stretch between two values, so nothing in between is
+//! lost and [`span`](Compressor::span) gives back the real lengths.
+
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// Sorted cells over one axis, each covering a range of world values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compressor<T> {
+    /// First and last world value of each cell, in order
+    cells: Vec<(T, T)>,
+}
+
+impl<T> Compressor<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    /// One cell for each distinct value
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let cells = Self::axis(values).into_iter().map(|v| (v, v)).collect();
+        Self { cells }
+    }
+
+    /// One cell for each distinct value, plus one for the values strictly
+    /// between each neighbouring pair, if there are any
+    pub fn with_gaps(values: impl IntoIterator<Item = T>) -> Self {
+        let one = T::from(1);
+        let axis = Self::axis(values);
+        let mut cells = Vec::with_capacity(axis.len() * 2);
+        for (i, &value) in axis.iter().enumerate() {
+            if let Some(&prev) = i.checked_sub(1).and_then(|i| axis.get(i))
+                && value - prev > one
+            {
+                cells.push((prev + one, value - one));
+            }
+            cells.push((value, value));
+        }
+        Self { cells }
+    }
+
+    fn axis(values: impl IntoIterator<Item = T>) -> Vec<T> {
+        let mut axis: Vec<T> = values.into_iter().collect();
+        axis.sort_unstable();
+        axis.dedup();
+        axis
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cell holding `value`, or `None` if no cell covers it
+    pub fn index(&self, value: T) -> Option<usize> {
+        let idx = self
+            .cells
+            .partition_point(|&(start, _)| start <= value)
+            .checked_sub(1)?;
+        (value <= self.cells[idx].1).then_some(idx)
+    }
+
+    /// First world value of cell `idx`, which is its only one unless it's a
+    /// gap. Panics if there's no such cell.
+    pub fn value(&self, idx: usize) -> T {
+        self.cells[idx].0
+    }
+
+    /// Every world value cell `idx` covers
+    pub fn range(&self, idx: usize) -> RangeInclusive<T> {
+        let (start, end) = self.cells[idx];
+        start..=end
+    }
+
+    /// How many world values cell `idx` covers
+    pub fn span(&self, idx: usize) -> T {
+        let (start, end) = self.cells[idx];
+        end - start + T::from(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress() {
+        let xs = Compressor::new([30_i64, -5, 7, 30]);
+        assert_eq!(xs.len(), 3);
+        assert_eq!(xs.index(7), Some(1));
+        assert_eq!(xs.index(8), None);
+        assert_eq!(xs.index(-6), None);
+        assert_eq!((xs.value(0), xs.value(2)), (-5, 30));
+
+        let gaps = Compressor::with_gaps([1_u64, 2, 10]);
+        assert_eq!(gaps.len(), 4);
+        assert_eq!(gaps.index(2), Some(1));
+        assert_eq!(gaps.index(5), Some(2));
+        assert_eq!(gaps.index(11), None);
+        assert_eq!(gaps.range(2), 3..=9);
+        assert_eq!((0..gaps.len()).map(|i| gaps.span(i)).sum::<u64>(), 10);
+
+        assert!(Compressor::<i32>::with_gaps([]).is_empty());
+    }
+}
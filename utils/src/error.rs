@@ -0,0 +1,163 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Where in the input something went wrong. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    /// The offending piece of input, empty if there wasn't one
+    pub token: String,
+}
+
+/// A problem with the puzzle input, with its position when known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// 1-based column of `token` within `line`. `token` is normally a slice of
+/// `line`, which pins down exactly which occurrence is meant; otherwise the
+/// first match is used.
+fn column_of(line: &str, token: &str) -> usize {
+    let line_start = line.as_ptr() as usize;
+    let token_start = token.as_ptr() as usize;
+    let offset =
+        if token_start >= line_start && token_start + token.len() <= line_start + line.len() {
+            token_start - line_start
+        } else {
+            line.find(token).unwrap_or(0)
+        };
+    line[..offset].chars().count() + 1
+}
+
+impl Error {
+    /// An error without a position, e.g. about the input as a whole.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// An error about `token` on line `line_no` (1-based), whose full text is
+    /// `line`.
+    pub fn at(line_no: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: Some(Location {
+                line: line_no,
+                column: column_of(line, token),
+                token: token.to_string(),
+            }),
+        }
+    }
+
+    /// Point an error that has no position yet at a whole line. Useful when a
+    /// helper only saw one line and didn't know its number.
+    pub fn on_line(self, line_no: usize, line: &str) -> Self {
+        if self.location.is_some() {
+            return self;
+        }
+        let token = line.trim();
+        Self::at(line_no, line, token, self.message)
+    }
+
+    /// Multi-line diagnostic quoting the offending line of `input` with the
+    /// token underlined, in the style of rustc.
+    pub fn render(&self, input: &str) -> String {
+        let Some(loc) = &self.location else {
+            return format!("error: {}", self.message);
+        };
+        let Some(source) = input.lines().nth(loc.line.saturating_sub(1)) else {
+            return format!("error: {self}");
+        };
+
+        let number = loc.line.to_string();
+        let pad = " ".repeat(number.len());
+        let underline = "^".repeat(loc.token.chars().count().max(1));
+        format!(
+            "error: {}\n{pad}--> line {}, column {}\n{pad} |\n{number} | {source}\n{pad} | {}{underline}",
+            self.message,
+            loc.line,
+            loc.column,
+            " ".repeat(loc.column - 1)
+        )
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(loc) if loc.token.is_empty() => {
+                write!(
+                    f,
+                    "line {}, column {}: {}",
+                    loc.line, loc.column, self.message
+                )
+            }
+            Some(loc) => write!(
+                f,
+                "line {}, column {}: {} (found '{}')",
+                loc.line, loc.column, self.message, loc.token
+            ),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+/// Parse `token`, a slice of line `line_no`, reporting `expected` (e.g.
+/// "a number") at its position if it doesn't parse.
+pub fn parse_at<T: FromStr>(line_no: usize, line: &str, token: &str, expected: &str) -> Result<T> {
+    token
+        .trim()
+        .parse()
+        .map_err(|_| Error::at(line_no, line, token.trim(), format!("expected {expected}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let line = "12,x4,7";
+        let token = line.split(',').nth(1).unwrap();
+        let err = parse_at::<i64>(3, line, token, "a number").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 4: expected a number (found 'x4')"
+        );
+        assert_eq!(
+            err.render("a\nb\n12,x4,7\n"),
+            "error: expected a number\n --> line 3, column 4\n  |\n3 | 12,x4,7\n  |    ^^"
+        );
+    }
+
+    #[test]
+    fn test_on_line() {
+        let err = Error::new("bad direction").on_line(2, "  L9x");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: bad direction (found 'L9x')"
+        );
+        assert_eq!(Error::new("empty input").to_string(), "empty input");
+    }
+}
@@ -0,0 +1,270 @@
+//! Points and directions for grids and coordinate puzzles.
+//!
+//! Points are `x` then `y`, and on a [`Grid`](crate::Grid) `x` is the column
+//! and `y` the row, so [`Dir4::Up`] is towards `y - 1`.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Point2<U> {
+        Point2::new(f(self.x), f(self.y))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Point2<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Point3<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T> From<[T; 2]> for Point2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<[T; 3]> for Point3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// Component-wise operators, plus scaling by a single value
+macro_rules! point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $point { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $point { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $point { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)+
+            }
+        }
+    };
+}
+
+point_ops!(Point2 { x, y });
+point_ops!(Point3 { x, y, z });
+
+/// The four orthogonal directions, clockwise from up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// All eight directions, clockwise from up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// Turn 90° clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turn 90° anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// One step in this direction, as `(dx, dy)`
+    pub fn offset(self) -> Point2<i8> {
+        Dir8::from(self).offset()
+    }
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// Turn 45° clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turn 45° anticlockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// One step in this direction, as `(dx, dy)`
+    pub fn offset(self) -> Point2<i8> {
+        let (x, y) = match self {
+            Dir8::Up => (0, -1),
+            Dir8::UpRight => (1, -1),
+            Dir8::Right => (1, 0),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (0, 1),
+            Dir8::DownLeft => (-1, 1),
+            Dir8::Left => (-1, 0),
+            Dir8::UpLeft => (-1, -1),
+        };
+        Point2::new(x, y)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+/// Take one step from a signed point
+impl<T: Add<Output = T> + From<i8>> Add<Dir4> for Point2<T> {
+    type Output = Self;
+
+    fn add(self, dir: Dir4) -> Self {
+        self + dir.offset().map(T::from)
+    }
+}
+
+impl<T: Add<Output = T> + From<i8>> Add<Dir8> for Point2<T> {
+    type Output = Self;
+
+    fn add(self, dir: Dir8) -> Self {
+        self + dir.offset().map(T::from)
+    }
+}
+
+/// Integers that can be grid coordinates. Negative values, and values too
+/// big for `usize`, are just off the grid.
+pub trait Coord: Copy {
+    fn to_index(self) -> Option<usize>;
+}
+
+macro_rules! coord {
+    ($($t:ty),+) => {
+        $(impl Coord for $t {
+            fn to_index(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+        })+
+    };
+}
+
+coord!(usize, u8, u16, u32, u64, isize, i8, i16, i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::Up);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownRight.opposite(), Dir8::UpLeft);
+        assert_eq!(Dir8::from(Dir4::Down), Dir8::Down);
+    }
+
+    #[test]
+    fn test_ops() {
+        let p = Point2::new(3_i64, 4);
+        assert_eq!(p + Dir4::Up, Point2::new(3, 3));
+        assert_eq!(p + Dir8::DownLeft, Point2::new(2, 5));
+        assert_eq!(p - Point2::new(1, 1), Point2::new(2, 3));
+        assert_eq!(-p * 2, Point2::new(-6, -8));
+        assert_eq!(p.dot(p), 25);
+
+        let mut q = Point3::from([1_i64, 2, 3]);
+        q += Point3::new(1, 1, 1);
+        assert_eq!(q, Point3::new(2, 3, 4));
+        assert_eq!((q - Point3::new(2, 0, 0)).dot(q), 3 * 3 + 4 * 4);
+    }
+}
@@ -0,0 +1,748 @@
+//! Rectangular grids, stored row by row in a single `Vec`.
+//!
+//! Cells can be addressed as `(row, col)` or as a [`Point2`], where `x` is
+//! the column and `y` the row. Points may be signed; any that fall off the
+//! grid are treated like any other out of bounds position.
+
+use std::ops::{Index, IndexMut};
+
+use crate::error::Error;
+use crate::geom::{Coord, Point2};
+
+/// Offsets of the orthogonal neighbours: up, right, down, left, the same
+/// order as [`Dir4::ALL`](crate::geom::Dir4::ALL).
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets of all eight neighbours, clockwise from up like
+/// [`Dir8::ALL`](crate::geom::Dir8::ALL).
+const ALL: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// Which cells count as touching: [`Four`](Self::Four) only shares edges,
+/// [`Eight`](Self::Eight) adds the diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &ORTHOGONAL,
+            Connectivity::Eight => &ALL,
+        }
+    }
+}
+
+/// Connected regions of a grid, from [`Grid::components`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// Region of each cell, numbered in the order their first cells appear
+    /// row by row. `None` for cells outside every region.
+    pub labels: Grid<Option<usize>>,
+    /// Number of cells in each region
+    pub sizes: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row `r` is `cells[r * width..(r + 1) * width]`
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from its rows, which must all be as wide as the first.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (idx, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::new(format!(
+                    "row {} is {} cells wide, expected {width} like the first row",
+                    idx + 1,
+                    row.len()
+                )));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Grid of `rows` × `cols` cells, given row by row.
+    ///
+    /// Panics if there aren't exactly `rows * cols` cells.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            rows * cols,
+            "a grid of {rows} rows by {cols} columns needs {} cells",
+            rows * cols
+        );
+        Self {
+            width: cols,
+            height: rows,
+            cells,
+        }
+    }
+
+    /// Grid of `rows` × `cols` cells, each `f(row, col)`.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| f(row, col))
+            .collect();
+        Self::from_vec(rows, cols, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of rows, the same as [`height`](Self::height)
+    pub fn rows(&self) -> usize {
+        self.height
+    }
+
+    /// Number of columns, the same as [`width`](Self::width)
+    pub fn cols(&self) -> usize {
+        self.width
+    }
+
+    pub fn in_bounds(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        self.in_bounds(row, col).then(|| row * self.width + col)
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.index_of(row, col).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.index_of(row, col).map(|i| &mut self.cells[i])
+    }
+
+    /// Every cell, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Row `row` as a slice. Panics if it's out of bounds.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {row} is out of bounds");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.height, "row {row} is out of bounds");
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Each row as a slice, top to bottom
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// The cells of column `col`, top to bottom. Panics if it's out of bounds.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} is out of bounds");
+        self.cells[col..].iter().step_by(self.width)
+    }
+
+    fn offset_coords<'a>(
+        &'a self,
+        row: usize,
+        col: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let r = row.checked_add_signed(dr)?;
+            let c = col.checked_add_signed(dc)?;
+            self.in_bounds(r, c).then_some((r, c))
+        })
+    }
+
+    /// Coordinates of the neighbours up, right, down and left that are on
+    /// the grid
+    pub fn neighbor_coords_4(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_coords(row, col, &ORTHOGONAL)
+    }
+
+    /// Neighbours with their values (4-directional: up, right, down, left)
+    pub fn neighbors_4(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize, &T)> + '_ {
+        self.neighbor_coords_4(row, col)
+            .map(|(r, c)| (r, c, &self.cells[r * self.width + c]))
+    }
+
+    /// Coordinates of the neighbours on the grid, including diagonals
+    pub fn neighbor_coords_8(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_coords(row, col, &ALL)
+    }
+
+    /// Neighbours with their values (8-directional: including diagonals)
+    pub fn neighbors_8(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize, &T)> + '_ {
+        self.neighbor_coords_8(row, col)
+            .map(|(r, c)| (r, c, &self.cells[r * self.width + c]))
+    }
+
+    /// `(row, col)` of `p`, if it's on the grid
+    fn locate<C: Coord>(&self, p: Point2<C>) -> Option<(usize, usize)> {
+        let (row, col) = (p.y.to_index()?, p.x.to_index()?);
+        self.in_bounds(row, col).then_some((row, col))
+    }
+
+    pub fn contains<C: Coord>(&self, p: Point2<C>) -> bool {
+        self.locate(p).is_some()
+    }
+
+    pub fn at<C: Coord>(&self, p: Point2<C>) -> Option<&T> {
+        self.locate(p)
+            .map(|(row, col)| &self.cells[row * self.width + col])
+    }
+
+    pub fn at_mut<C: Coord>(&mut self, p: Point2<C>) -> Option<&mut T> {
+        self.locate(p)
+            .map(|(row, col)| &mut self.cells[row * self.width + col])
+    }
+
+    /// Neighbours of `p` up, right, down and left, with their values
+    pub fn neighbors_4_at<C: Coord>(
+        &self,
+        p: Point2<C>,
+    ) -> impl Iterator<Item = (Point2<usize>, &T)> + '_ {
+        self.locate(p)
+            .into_iter()
+            .flat_map(|(row, col)| self.neighbors_4(row, col))
+            .map(|(r, c, cell)| (Point2::new(c, r), cell))
+    }
+
+    /// Neighbours of `p` including diagonals, with their values
+    pub fn neighbors_8_at<C: Coord>(
+        &self,
+        p: Point2<C>,
+    ) -> impl Iterator<Item = (Point2<usize>, &T)> + '_ {
+        self.locate(p)
+            .into_iter()
+            .flat_map(|(row, col)| self.neighbors_8(row, col))
+            .map(|(r, c, cell)| (Point2::new(c, r), cell))
+    }
+
+    /// Every position on the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point2<usize>> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    /// The `height` × `width` rectangle whose top left cell is at
+    /// `(row, col)`. Panics if it doesn't fit on the grid.
+    pub fn view(&self, row: usize, col: usize, height: usize, width: usize) -> GridView<'_, T> {
+        assert!(
+            row + height <= self.height && col + width <= self.width,
+            "a {width}x{height} view at ({row}, {col}) doesn't fit a {}x{} grid",
+            self.width,
+            self.height
+        );
+        GridView {
+            grid: self,
+            row,
+            col,
+            width,
+            height,
+        }
+    }
+
+    /// Every cell with its coordinates, row by row
+    pub fn iter_cells(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (i / width, i % width, cell))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Cells that `include` accepts and that can be reached from `start`
+    /// through other such cells. Empty if `start` itself isn't one.
+    pub fn flood_fill<C: Coord>(
+        &self,
+        start: Point2<C>,
+        include: impl Fn(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> Grid<bool> {
+        let mut filled = Grid::filled(self.height, self.width, false);
+        self.fill(self.locate(start), &include, connectivity, &mut filled);
+        filled
+    }
+
+    /// Like [`flood_fill`](Self::flood_fill), but from every accepted cell on
+    /// the edge of the grid: the part of the region that's open to the
+    /// outside, rather than enclosed.
+    pub fn exterior(&self, include: impl Fn(&T) -> bool, connectivity: Connectivity) -> Grid<bool> {
+        let mut filled = Grid::filled(self.height, self.width, false);
+        let (last_row, last_col) = (self.height.saturating_sub(1), self.width.saturating_sub(1));
+        let edge = (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|&(row, col)| row == 0 || col == 0 || row == last_row || col == last_col);
+        self.fill(edge, &include, connectivity, &mut filled);
+        filled
+    }
+
+    /// Split the cells `include` accepts into connected regions.
+    pub fn components(
+        &self,
+        include: impl Fn(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> Components {
+        let mut seen = Grid::filled(self.height, self.width, false);
+        let mut labels = Grid::filled(self.height, self.width, None);
+        let mut sizes = Vec::new();
+        let mut stack = Vec::new();
+
+        for i in 0..self.cells.len() {
+            if seen.cells[i] || !include(&self.cells[i]) {
+                continue;
+            }
+            let label = sizes.len();
+            let mut size = 0;
+            seen.cells[i] = true;
+            stack.push((i / self.width, i % self.width));
+            while let Some((row, col)) = stack.pop() {
+                labels.cells[row * self.width + col] = Some(label);
+                size += 1;
+                for (r, c) in self.offset_coords(row, col, connectivity.offsets()) {
+                    let j = r * self.width + c;
+                    if !seen.cells[j] && include(&self.cells[j]) {
+                        seen.cells[j] = true;
+                        stack.push((r, c));
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+
+        Components { labels, sizes }
+    }
+
+    /// Mark everything reachable from `starts` in `filled`
+    fn fill(
+        &self,
+        starts: impl IntoIterator<Item = (usize, usize)>,
+        include: &impl Fn(&T) -> bool,
+        connectivity: Connectivity,
+        filled: &mut Grid<bool>,
+    ) {
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        let mut visit = |row: usize, col: usize, stack: &mut Vec<_>| {
+            let i = row * self.width + col;
+            if !filled.cells[i] && include(&self.cells[i]) {
+                filled.cells[i] = true;
+                stack.push((row, col));
+            }
+        };
+
+        for (row, col) in starts {
+            visit(row, col, &mut stack);
+        }
+        while let Some((row, col)) = stack.pop() {
+            for (r, c) in self.offset_coords(row, col, connectivity.offsets()) {
+                visit(r, c, &mut stack);
+            }
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid of `rows` × `cols`, filled with `value`.
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self {
+        Self::from_vec(rows, cols, vec![value; rows * cols])
+    }
+
+    /// Rows become columns: `(row, col)` moves to `(col, row)`.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.width, self.height, |row, col| {
+            self.cells[col * self.width + row].clone()
+        })
+    }
+
+    /// Turned a quarter clockwise, so the first column becomes the top row.
+    pub fn rotate_cw(&self) -> Self {
+        Self::from_fn(self.width, self.height, |row, col| {
+            self.cells[(self.height - 1 - col) * self.width + row].clone()
+        })
+    }
+
+    /// Turned a quarter anticlockwise, so the last column becomes the top row.
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_fn(self.width, self.height, |row, col| {
+            self.cells[col * self.width + self.width - 1 - row].clone()
+        })
+    }
+
+    /// Mirrored left to right.
+    pub fn flip_h(&self) -> Self {
+        Self::from_fn(self.height, self.width, |row, col| {
+            self.cells[row * self.width + self.width - 1 - col].clone()
+        })
+    }
+
+    /// Mirrored top to bottom.
+    pub fn flip_v(&self) -> Self {
+        Self::from_fn(self.height, self.width, |row, col| {
+            self.cells[(self.height - 1 - row) * self.width + col].clone()
+        })
+    }
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    /// The distinct ways this grid can be rotated and flipped, starting with
+    /// the grid itself. A symmetric grid has fewer than the full 8.
+    pub fn symmetries(&self) -> impl Iterator<Item = Self> + use<T> {
+        let mut found: Vec<Self> = Vec::with_capacity(8);
+        let mut grid = self.clone();
+        for _ in 0..2 {
+            for _ in 0..4 {
+                let next = grid.rotate_cw();
+                if !found.contains(&grid) {
+                    found.push(grid);
+                }
+                grid = next;
+            }
+            grid = grid.flip_h();
+        }
+        found.into_iter()
+    }
+}
+
+impl<T: std::fmt::Display> Grid<T> {
+    pub fn pretty_print(&self) {
+        for row in self.iter_rows() {
+            for cell in row {
+                print!("{cell}");
+            }
+            println!();
+        }
+    }
+}
+
+/// `grid[row]` is that row's slice, so `grid[row][col]` is a cell
+impl<T> Index<usize> for Grid<T> {
+    type Output = [T];
+
+    fn index(&self, row: usize) -> &[T] {
+        self.row(row)
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, row: usize) -> &mut [T] {
+        self.row_mut(row)
+    }
+}
+
+/// `grid[p]` for a point on the grid. Panics if it's off the grid.
+impl<T, C: Coord> Index<Point2<C>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2<C>) -> &T {
+        self.at(p).expect("point is off the grid")
+    }
+}
+
+impl<T, C: Coord> IndexMut<Point2<C>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2<C>) -> &mut T {
+        self.at_mut(p).expect("point is off the grid")
+    }
+}
+
+/// A borrowed rectangle of a [`Grid`], with `(0, 0)` at its own top left.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    row: usize,
+    col: usize,
+    width: usize,
+    height: usize,
+}
+
+// Derived `Clone` and `Copy` would need `T: Copy` too
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
+        if !self.in_bounds(row, col) {
+            return None;
+        }
+        self.grid.get(self.row + row, self.col + col)
+    }
+
+    pub fn at<C: Coord>(&self, p: Point2<C>) -> Option<&'a T> {
+        self.get(p.y.to_index()?, p.x.to_index()?)
+    }
+
+    /// Row `row` of the view as a slice. Panics if it's out of bounds.
+    pub fn row(&self, row: usize) -> &'a [T] {
+        assert!(row < self.height, "row {row} is out of bounds");
+        &self.grid.row(self.row + row)[self.col..self.col + self.width]
+    }
+
+    /// Each row as a slice, top to bottom
+    pub fn iter_rows(&self) -> impl Iterator<Item = &'a [T]> + use<'a, T> {
+        let view = *self;
+        (0..self.height).map(move |row| view.row(row))
+    }
+
+    /// Every cell with its coordinates in the view, row by row
+    pub fn iter_cells(&self) -> impl Iterator<Item = (usize, usize, &'a T)> + use<'a, T> {
+        self.iter_rows().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(move |(col, cell)| (row, col, cell))
+        })
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copy the view out into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |row, col| {
+            self.row(row)[col].clone()
+        })
+    }
+}
+
+impl<T> Index<usize> for GridView<'_, T> {
+    type Output = [T];
+
+    fn index(&self, row: usize) -> &[T] {
+        self.row(row)
+    }
+}
+
+impl<T, C: Coord> Index<Point2<C>> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, p: Point2<C>) -> &T {
+        self.at(p).expect("point is off the view")
+    }
+}
+
+/// One cell per character, one row per line. Blank lines at the end are
+/// ignored; any other line must be as wide as the first.
+pub fn parse_char_grid(input: &str) -> Result<Grid<char>, Error> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+
+    let width = lines.first().map_or(0, |line| line.chars().count());
+    let mut cells = Vec::with_capacity(width * lines.len());
+    for (idx, line) in lines.iter().enumerate() {
+        let len = cells.len();
+        cells.extend(line.chars());
+        if cells.len() - len != width {
+            return Err(Error::at(
+                idx + 1,
+                line,
+                line,
+                format!("expected {width} characters like the first line"),
+            ));
+        }
+    }
+
+    Ok(Grid::from_vec(lines.len(), width, cells))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let grid = parse_char_grid("abc\ndef\n\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid.iter_rows().count(), 2);
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid[0][1], 'b');
+        assert_eq!(grid.get(1, 3), None);
+
+        let neighbours: Vec<_> = grid.neighbors_4(0, 1).map(|(_, _, &c)| c).collect();
+        assert_eq!(neighbours, vec!['c', 'e', 'a']);
+        assert_eq!(grid.neighbor_coords_8(1, 0).count(), 3);
+    }
+
+    #[test]
+    fn test_points() {
+        let mut grid = parse_char_grid("ab\ncd").unwrap();
+        assert_eq!(grid[Point2::new(1_usize, 0)], 'b');
+        assert_eq!(grid.at(Point2::new(-1_i64, 0)), None);
+        assert!(!grid.contains(Point2::new(0_i32, 2)));
+
+        grid[Point2::new(0_i64, 1)] = 'x';
+        assert_eq!(grid.row(1), &['x', 'd']);
+
+        let around: Vec<_> = grid.neighbors_4_at(Point2::new(1_i64, 1)).collect();
+        assert_eq!(
+            around,
+            vec![(Point2::new(1, 0), &'b'), (Point2::new(0, 1), &'x')]
+        );
+        assert_eq!(grid.points().last(), Some(Point2::new(1, 1)));
+    }
+
+    #[test]
+    fn test_constructors() {
+        // Every constructor takes rows, then columns
+        let numbered = Grid::from_fn(2, 3, |row, col| row * 3 + col);
+        assert_eq!((numbered.height(), numbered.width()), (2, 3));
+        assert_eq!(numbered, Grid::from_vec(2, 3, (0..6).collect()));
+        let zeros = Grid::filled(2, 3, 0);
+        assert_eq!((zeros.height(), zeros.width()), (2, 3));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = parse_char_grid("abc\ndef").unwrap();
+        let text = |g: &Grid<char>| {
+            g.iter_rows()
+                .map(|r| r.iter().collect::<String>())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(text(&grid.rotate_cw()), ["da", "eb", "fc"]);
+        assert_eq!(text(&grid.rotate_ccw()), ["cf", "be", "ad"]);
+        assert_eq!(text(&grid.transpose()), ["ad", "be", "cf"]);
+        assert_eq!(text(&grid.flip_h()), ["cba", "fed"]);
+        assert_eq!(text(&grid.flip_v()), ["def", "abc"]);
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+
+        assert_eq!(grid.symmetries().count(), 8);
+        let square = parse_char_grid("#.\n.#").unwrap();
+        assert_eq!(square.symmetries().count(), 2);
+        let blank = parse_char_grid("..\n..").unwrap();
+        assert_eq!(blank.symmetries().collect::<Vec<_>>(), vec![blank]);
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = parse_char_grid("abcd\nefgh\nijkl").unwrap();
+        let view = grid.view(1, 1, 2, 2);
+        assert_eq!((view.width(), view.height()), (2, 2));
+        assert_eq!(view[0], ['f', 'g']);
+        assert_eq!(view[Point2::new(1_i64, 1)], 'k');
+        assert_eq!(view.get(0, 2), None);
+        assert_eq!(view.at(Point2::new(-1_i64, 0)), None);
+        assert_eq!(view.to_grid(), parse_char_grid("fg\njk").unwrap());
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = parse_char_grid("##..\n#..#\n..##\n.#..").unwrap();
+        let dots = |&c: &char| c == '.';
+
+        let fill = grid.flood_fill(Point2::new(2_i64, 0), dots, Connectivity::Four);
+        assert_eq!(fill.cells().iter().filter(|&&f| f).count(), 7);
+        let none = grid.flood_fill(Point2::new(0_i64, 0), dots, Connectivity::Four);
+        assert!(none.cells().iter().all(|&f| !f));
+
+        let walls = grid.components(|&c| c == '#', Connectivity::Four);
+        assert_eq!(walls.sizes, vec![3, 3, 1]);
+        assert_eq!(walls.labels[Point2::new(3_usize, 2)], Some(1));
+        assert_eq!(walls.labels[Point2::new(2_usize, 0)], None);
+        let walls = grid.components(|&c| c == '#', Connectivity::Eight);
+        assert_eq!(walls.sizes, vec![3, 4]);
+
+        let ring = parse_char_grid(".....\n.###.\n.#.#.\n.###.").unwrap();
+        let outside = ring.exterior(|&c| c == '.', Connectivity::Four);
+        assert!(outside[Point2::new(0_usize, 3)]);
+        assert!(!outside[Point2::new(2_usize, 2)]);
+
+        // Only a diagonal step gets out of this hole
+        let gapped = parse_char_grid(".....\n.##..\n.#.#.\n..##.\n.....").unwrap();
+        let hole = Point2::new(2_usize, 2);
+        let outside_4 = gapped.exterior(|&c| c == '.', Connectivity::Four);
+        let outside_8 = gapped.exterior(|&c| c == '.', Connectivity::Eight);
+        assert!(!outside_4[hole]);
+        assert!(outside_8[hole]);
+        assert_ne!(outside_4, outside_8);
+    }
+
+    #[test]
+    fn test_ragged() {
+        let err = parse_char_grid("abc\nde\nfgh").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected 3 characters like the first line (found 'de')"
+        );
+
+        let err = Grid::from_rows(vec![vec![1, 2], vec![3]]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "row 2 is 1 cells wide, expected 2 like the first row"
+        );
+    }
+}
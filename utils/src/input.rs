@@ -0,0 +1,148 @@
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing at a directory of `dayN.txt` files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+/// The workspace root, so paths work no matter which directory a binary is
+/// started from. `utils` sits directly below the root.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("utils crate should live inside the workspace")
+}
+
+/// Resolve a relative path against the workspace root, leaving absolute
+/// paths alone.
+pub fn from_root(path: impl AsRef<Path>) -> PathBuf {
+    workspace_root().join(path)
+}
+
+/// Directory holding the `dayN.txt` inputs: `$AOC_INPUT_DIR` if set,
+/// otherwise `inputs/` in the workspace root.
+pub fn input_dir() -> PathBuf {
+    input_dir_from(env::var_os(INPUT_DIR_VAR))
+}
+
+/// [`input_dir`] given the value of `$AOC_INPUT_DIR`, which is relative to
+/// the workspace root like any other path
+fn input_dir_from(var: Option<OsString>) -> PathBuf {
+    match var {
+        Some(dir) if !dir.is_empty() => from_root(dir),
+        _ => from_root("inputs"),
+    }
+}
+
+/// Work out where to read a day's input from.
+///
+/// `arg` is the optional path given on the command line: `-` means stdin,
+/// and a relative path is looked up from the workspace root, so it means the
+/// same wherever the binary is run from. Only if there's no such file there
+/// is it tried from the current directory. Without `arg` the day's file in
+/// [`input_dir`] is used.
+pub fn resolve(day: u8, arg: Option<&str>) -> InputSource {
+    match arg {
+        Some("-") => InputSource::Stdin,
+        Some(path) => {
+            let path = Path::new(path);
+            let rooted = from_root(path);
+            if path.is_relative() && !rooted.exists() && path.exists() {
+                InputSource::File(path.to_path_buf())
+            } else {
+                InputSource::File(rooted)
+            }
+        }
+        None => InputSource::File(input_dir().join(format!("day{day}.txt"))),
+    }
+}
+
+impl InputSource {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::File(path) => std::fs::read_to_string(path).map_err(|e| {
+                let hint = if e.kind() == io::ErrorKind::NotFound {
+                    format!(" (pass a path, '-' for stdin, or set {INPUT_DIR_VAR})")
+                } else {
+                    String::new()
+                };
+                io::Error::new(e.kind(), format!("{}: {}{}", path.display(), e, hint))
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Read the default input for a day, see [`resolve`].
+pub fn load_day(day: u8) -> io::Result<String> {
+    resolve(day, None).read()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tests run from the utils directory, which has its own Cargo.toml
+    #[test]
+    fn test_resolve() {
+        let root = workspace_root();
+        assert_eq!(resolve(1, Some("-")), InputSource::Stdin);
+        assert_eq!(
+            resolve(1, Some("/tmp/day1.txt")),
+            InputSource::File(PathBuf::from("/tmp/day1.txt"))
+        );
+        assert_eq!(
+            resolve(1, Some("Cargo.toml")),
+            InputSource::File(root.join("Cargo.toml"))
+        );
+        assert_eq!(
+            resolve(1, Some("inputs/missing.txt")),
+            InputSource::File(root.join("inputs/missing.txt"))
+        );
+        // Only in the current directory
+        assert_eq!(
+            resolve(1, Some("src/input.rs")),
+            InputSource::File(PathBuf::from("src/input.rs"))
+        );
+        assert_eq!(
+            resolve(7, None),
+            InputSource::File(input_dir().join("day7.txt"))
+        );
+    }
+
+    #[test]
+    fn test_input_dir() {
+        let root = workspace_root();
+        assert_eq!(input_dir_from(None), root.join("inputs"));
+        assert_eq!(input_dir_from(Some("".into())), root.join("inputs"));
+        assert_eq!(
+            input_dir_from(Some("examples".into())),
+            root.join("examples")
+        );
+        assert_eq!(
+            input_dir_from(Some("/srv/aoc".into())),
+            PathBuf::from("/srv/aoc")
+        );
+    }
+}
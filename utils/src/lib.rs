@@ -1,3 +1,24 @@
+pub mod alloc;
+pub mod animate;
+pub mod compress;
+pub mod error;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod policy;
+pub mod render;
+pub mod search;
+pub mod solution;
+pub mod sparse;
+
+pub use compress::Compressor;
+pub use error::Error;
+pub use grid::{Components, Connectivity, Grid, GridView, parse_char_grid};
+pub use parse::sections;
+pub use solution::{Answer, PartResult, Parts, Solution, Timings, run, solve};
+pub use sparse::SparseGrid;
+
 /// Read input file as a string
 pub fn read_input(path: &str) -> std::io::Result<String> {
     std::fs::read_to_string(path)
@@ -72,139 +93,6 @@ pub fn transpose_matrix<T: Clone>(rows: &[Vec<T>]) -> Vec<Vec<T>> {
         .collect()
 }
 
-#[derive(Debug, Clone)]
-pub struct Grid<T> {
-    data: Vec<Vec<T>>,
-}
-
-impl<T> Grid<T> {
-    pub fn new(data: Vec<Vec<T>>) -> Self {
-        Self { data }
-    }
-
-    pub fn rows(&self) -> usize {
-        self.data.len()
-    }
-
-    pub fn cols(&self) -> usize {
-        self.data.get(0).map(|row| row.len()).unwrap_or(0)
-    }
-
-    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
-        self.data.get(row).and_then(|r| r.get(col))
-    }
-
-    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
-        self.data.get_mut(row).and_then(|r| r.get_mut(col))
-    }
-
-    /// Get coordinates of neighbors (4-directional: up, down, left, right)
-    pub fn neighbor_coords_4(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
-        let mut result = Vec::new();
-        let rows = self.rows();
-        let cols = self.cols();
-
-        if row > 0 {
-            result.push((row - 1, col));
-        }
-        if row + 1 < rows {
-            result.push((row + 1, col));
-        }
-        if col > 0 {
-            result.push((row, col - 1));
-        }
-        if col + 1 < cols {
-            result.push((row, col + 1));
-        }
-        result
-    }
-
-    /// Get neighbors with their values (4-directional: up, down, left, right)
-    /// Returns Vec<(row, col, &value)>
-    pub fn neighbors_4(&self, row: usize, col: usize) -> Vec<(usize, usize, &T)> {
-        self.neighbor_coords_4(row, col)
-            .into_iter()
-            .filter_map(|(r, c)| self.get(r, c).map(|val| (r, c, val)))
-            .collect()
-    }
-
-    /// Get coordinates of neighbors (8-directional: including diagonals)
-    pub fn neighbor_coords_8(&self, row: usize, col: usize) -> Vec<(usize, usize)> {
-        let mut result = Vec::new();
-        let rows = self.rows() as i32;
-        let cols = self.cols() as i32;
-
-        for dr in -1..=1 {
-            for dc in -1..=1 {
-                if dr == 0 && dc == 0 {
-                    continue;
-                }
-                let new_row = row as i32 + dr;
-                let new_col = col as i32 + dc;
-                if new_row >= 0 && new_row < rows && new_col >= 0 && new_col < cols {
-                    result.push((new_row as usize, new_col as usize));
-                }
-            }
-        }
-        result
-    }
-
-    /// Get neighbors with their values (8-directional: including diagonals)
-    /// Returns Vec<(row, col, &value)>
-    pub fn neighbors_8(&self, row: usize, col: usize) -> Vec<(usize, usize, &T)> {
-        self.neighbor_coords_8(row, col)
-            .into_iter()
-            .filter_map(|(r, c)| self.get(r, c).map(|val| (r, c, val)))
-            .collect()
-    }
-
-    pub fn iter_cells(&self) -> impl Iterator<Item = (usize, usize, &T)> {
-        self.data.iter().enumerate().flat_map(|(row_idx, row)| {
-            row.iter()
-                .enumerate()
-                .map(move |(col_idx, cell)| (row_idx, col_idx, cell))
-        })
-    }
-}
-
-impl<T> std::ops::Index<usize> for Grid<T> {
-    type Output = Vec<T>;
-
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.data[index]
-    }
-}
-
-impl<T: std::fmt::Display> Grid<T> {
-    pub fn pretty_print(&self) {
-        for row in &self.data {
-            for cell in row {
-                print!("{cell}");
-            }
-            println!();
-        }
-    }
-}
-
-impl<T: Clone> Grid<T> {
-    /// Create a grid of `rows` × `cols`, filled with `value`.
-    pub fn filled(rows: usize, cols: usize, value: T) -> Self {
-        let data = vec![vec![value; cols]; rows];
-        Self { data }
-    }
-}
-
-impl<T> std::ops::IndexMut<usize> for Grid<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.data[index]
-    }
-}
-
-pub fn parse_char_grid(input: &str) -> Grid<char> {
-    let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-    Grid::new(data)
-}
-
 use std::fmt;
 use std::ops::{Add, Div, Mul, Sub};
 
@@ -332,13 +220,7 @@ pub fn rref(mut mat: Vec<Vec<Rational>>) -> (Vec<Vec<Rational>>, Vec<Option<usiz
             break;
         }
 
-        let mut sel: Option<usize> = None;
-        for r in pivot_row..rows {
-            if !mat[r][col].is_zero() {
-                sel = Some(r);
-                break;
-            }
-        }
+        let sel = (pivot_row..rows).find(|&r| !mat[r][col].is_zero());
 
         let row = match sel {
             Some(r) => r,
@@ -350,20 +232,21 @@ pub fn rref(mut mat: Vec<Vec<Rational>>) -> (Vec<Vec<Rational>>, Vec<Option<usiz
         }
 
         let pivot_val = mat[pivot_row][col];
-        for c in col..cols {
-            mat[pivot_row][c] = mat[pivot_row][c] / pivot_val;
+        for v in &mut mat[pivot_row][col..] {
+            *v = *v / pivot_val;
         }
+        let pivot = mat[pivot_row].clone();
 
-        for r in 0..rows {
+        for (r, row) in mat.iter_mut().enumerate() {
             if r == pivot_row {
                 continue;
             }
-            let factor = mat[r][col];
+            let factor = row[col];
             if factor.is_zero() {
                 continue;
             }
-            for c in col..cols {
-                mat[r][c] = mat[r][c] - factor * mat[pivot_row][c];
+            for (v, &p) in row[col..].iter_mut().zip(&pivot[col..]) {
+                *v = *v - factor * p;
             }
         }
 
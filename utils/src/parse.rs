@@ -0,0 +1,401 @@
+//! Small combinators for the line based inputs most days have.
+//!
+//! A [`Line`] is a cursor over one line of input. Each combinator skips
+//! leading spaces, consumes what it matched and returns it, borrowed from the
+//! input where it can. When the line doesn't match, the [`Error`] points at
+//! where it stopped matching. A day's grammar then reads top to bottom, e.g.
+//! for `12x5: 1 0 1`:
+//!
+//! ```
+//! # use utils::parse::Line;
+//! let mut line = Line::new(1, "12x5: 1 0 1");
+//! let w: usize = line.int()?;
+//! line.expect("x")?;
+//! let h: usize = line.int()?;
+//! line.expect(":")?;
+//! let counts: Vec<usize> = line.many(Line::int)?;
+//! assert_eq!((w, h, counts), (12, 5, vec![1, 0, 1]));
+//! # Ok::<(), utils::Error>(())
+//! ```
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+/// Characters that end a token in error messages, so a bad number in
+/// `(1,x,3)` is reported as `x` rather than `x,3)`.
+const PUNCTUATION: &[char] = &[',', ';', ':', '(', ')', '[', ']', '{', '}', '<', '>', '='];
+
+/// Cursor over one line of input.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    line_no: usize,
+    /// The whole line, for error positions
+    text: &'a str,
+    /// What's left to parse, always a suffix of a slice of `text`
+    rest: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Cursor at the start of `text`, which is line `line_no` (1-based).
+    pub fn new(line_no: usize, text: &'a str) -> Self {
+        Self {
+            line_no,
+            text,
+            rest: text,
+        }
+    }
+
+    pub fn line_no(&self) -> usize {
+        self.line_no
+    }
+
+    /// The whole line, including anything already parsed
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// What hasn't been parsed yet
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    /// Whether only whitespace is left
+    pub fn is_done(&self) -> bool {
+        self.rest.trim().is_empty()
+    }
+
+    /// An error about `token`, which should be a slice of this line.
+    pub fn error(&self, token: &str, message: impl Into<String>) -> Error {
+        Error::at(self.line_no, self.text, token, message)
+    }
+
+    /// An error about whatever comes next, or the end of the line.
+    fn error_here(&self, message: impl Into<String>) -> Error {
+        self.error(self.next_token(), message)
+    }
+
+    /// The next token, for error messages only.
+    fn next_token(&self) -> &'a str {
+        let rest = self.rest.trim_start();
+        let len = match rest.chars().next() {
+            Some(c) if PUNCTUATION.contains(&c) => c.len_utf8(),
+            _ => rest
+                .find(|c: char| c.is_whitespace() || PUNCTUATION.contains(&c))
+                .unwrap_or(rest.len()),
+        };
+        &rest[..len]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// The next character, without consuming it.
+    pub fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest.chars().next()
+    }
+
+    /// Consume `c` if it comes next.
+    pub fn eat(&mut self, c: char) -> bool {
+        match self.peek() {
+            Some(next) if next == c => {
+                self.rest = &self.rest[c.len_utf8()..];
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Consume `literal`, which must come next.
+    pub fn expect(&mut self, literal: &str) -> Result<()> {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(literal) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.error_here(format!("expected '{literal}'"))),
+        }
+    }
+
+    /// Consume the longest run of characters matching `f`, possibly empty.
+    pub fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        self.skip_whitespace();
+        let len = self.rest.find(|c: char| !f(c)).unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    /// A run of non-whitespace characters.
+    pub fn word(&mut self) -> Result<&'a str> {
+        match self.take_while(|c| !c.is_whitespace()) {
+            "" => Err(self.error_here("expected a word")),
+            word => Ok(word),
+        }
+    }
+
+    /// An integer with an optional leading `-`.
+    pub fn int<T: FromStr>(&mut self) -> Result<T> {
+        self.skip_whitespace();
+        let sign = usize::from(self.rest.starts_with('-'));
+        let digits = self.rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len() - sign);
+        if digits == 0 {
+            return Err(self.error_here("expected a number"));
+        }
+
+        let (token, rest) = self.rest.split_at(sign + digits);
+        let value = token
+            .parse()
+            .map_err(|_| self.error(token, "number is out of range"))?;
+        self.rest = rest;
+        Ok(value)
+    }
+
+    /// Everything up to `delim`, trimmed, consuming the delimiter too.
+    pub fn until(&mut self, delim: char) -> Result<&'a str> {
+        self.skip_whitespace();
+        let Some((taken, rest)) = self.rest.split_once(delim) else {
+            return Err(self.error(self.rest.trim_end(), format!("expected '{delim}'")));
+        };
+        self.rest = rest;
+        Ok(taken.trim())
+    }
+
+    /// The key of a `key: value` line. The value is what's left on the line.
+    pub fn key(&mut self) -> Result<&'a str> {
+        match self.until(':')? {
+            "" => Err(self.error_here("expected a key before ':'")),
+            key => Ok(key),
+        }
+    }
+
+    /// A group such as `(1,3)`, returned as a cursor over what's between the
+    /// brackets. Groups don't nest.
+    pub fn bracketed(&mut self, open: char, close: char) -> Result<Line<'a>> {
+        if !self.eat(open) {
+            return Err(self.error_here(format!("expected '{open}'")));
+        }
+        let Some(end) = self.rest.find(close) else {
+            return Err(self.error(self.rest.trim_end(), format!("expected '{close}'")));
+        };
+        let inner = Line {
+            rest: &self.rest[..end],
+            ..*self
+        };
+        self.rest = &self.rest[end + close.len_utf8()..];
+        Ok(inner)
+    }
+
+    /// Items separated by `sep` up to the end of this cursor, e.g. the
+    /// contents of a group. Nothing at all is an empty list.
+    pub fn list<T>(
+        &mut self,
+        sep: char,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        if self.is_done() {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if !self.eat(sep) {
+                self.end()?;
+                return Ok(items);
+            }
+        }
+    }
+
+    /// Whitespace separated items up to the end of this cursor.
+    pub fn many<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        while !self.is_done() {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Run `parse` and also return the text it consumed, e.g. to point a
+    /// later error at a value that parsed fine but made no sense.
+    pub fn consumed<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<(T, &'a str)> {
+        self.skip_whitespace();
+        let start = self.rest;
+        let value = parse(self)?;
+        let len = start.len() - self.rest.len();
+        Ok((value, start[..len].trim_end()))
+    }
+
+    /// Fail unless only whitespace is left.
+    pub fn end(&mut self) -> Result<()> {
+        match self.is_done() {
+            true => Ok(()),
+            false => Err(self.error_here("unexpected text")),
+        }
+    }
+}
+
+/// Numbered cursors over the lines of `input` that aren't blank.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| Line::new(idx + 1, line))
+}
+
+/// A run of lines with no blank line in between, with line endings
+/// normalised to `\n` and trailing whitespace trimmed from every line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// Line number of the section's first line
+    pub line_no: usize,
+    /// Borrowed from the input unless normalising it changed something
+    pub text: Cow<'a, str>,
+}
+
+impl Section<'_> {
+    /// Numbered cursors over the section's lines.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        let first = self.line_no;
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(idx, line)| Line::new(first + idx, line))
+    }
+}
+
+/// The sections of `input`, split on blank lines. Runs of blank lines count
+/// as one and blank lines at either end are ignored, as are `\r\n` line
+/// endings and trailing whitespace.
+pub fn sections(input: &str) -> impl Iterator<Item = Section<'_>> {
+    let mut lines = input.lines().enumerate().peekable();
+
+    std::iter::from_fn(move || {
+        while lines.next_if(|(_, line)| line.trim().is_empty()).is_some() {}
+        let (first_idx, first) = lines.next()?;
+        let mut last = first;
+        let mut clean = first.trim_end().len() == first.len();
+        while let Some((_, line)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+            clean &= line.trim_end().len() == line.len();
+            last = line;
+        }
+
+        // Both ends are slices of `input`, so the section is everything
+        // between them, and can be borrowed as is unless it needs tidying
+        let start = first.as_ptr() as usize - input.as_ptr() as usize;
+        let end = last.as_ptr() as usize - input.as_ptr() as usize + last.len();
+        let raw = &input[start..end];
+        let text = match clean && !raw.contains('\r') {
+            true => Cow::Borrowed(raw),
+            false => Cow::Owned(
+                raw.lines()
+                    .map(str::trim_end)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        };
+
+        Some(Section {
+            line_no: first_idx + 1,
+            text,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grammar() {
+        let mut line = Line::new(1, "[.##.] (3) (1,3) () {3,-5,4}");
+        assert_eq!(line.bracketed('[', ']').unwrap().rest(), ".##.");
+
+        let mut buttons = Vec::new();
+        while line.peek() == Some('(') {
+            buttons.push(
+                line.bracketed('(', ')')
+                    .unwrap()
+                    .list(',', Line::int::<u8>)
+                    .unwrap(),
+            );
+        }
+        assert_eq!(buttons, vec![vec![3], vec![1, 3], vec![]]);
+
+        let target = line
+            .bracketed('{', '}')
+            .unwrap()
+            .list(',', Line::int::<i32>);
+        assert_eq!(target.unwrap(), vec![3, -5, 4]);
+        assert!(line.end().is_ok());
+
+        let mut line = Line::new(2, "aaa: you hhh");
+        assert_eq!(line.key().unwrap(), "aaa");
+        assert_eq!(line.many(Line::word).unwrap(), vec!["you", "hhh"]);
+
+        let mut line = Line::new(3, " 42,7");
+        assert_eq!(line.consumed(Line::int::<u8>).unwrap(), (42, "42"));
+        assert_eq!(line.rest(), ",7");
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = Line::new(4, "(1,x,3)")
+            .bracketed('(', ')')
+            .and_then(|mut group| group.list(',', Line::int::<u8>))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 4: expected a number (found 'x')"
+        );
+
+        let mut line = Line::new(1, "12 5");
+        line.int::<u8>().unwrap();
+        assert_eq!(
+            line.expect("x").unwrap_err().to_string(),
+            "line 1, column 4: expected 'x' (found '5')"
+        );
+
+        let err = Line::new(7, "1000").int::<u8>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 7, column 1: number is out of range (found '1000')"
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "\n0:\n##\n\n\n1:\n.#  \n\n4x4: 1 2\n";
+        let found: Vec<(usize, String)> = sections(input)
+            .map(|s| (s.line_no, s.text.into_owned()))
+            .collect();
+        let expected = [(2, "0:\n##"), (6, "1:\n.#"), (9, "4x4: 1 2")];
+        assert_eq!(found, expected.map(|(n, text)| (n, text.to_string())));
+
+        let numbers: Vec<usize> = sections(input)
+            .nth(1)
+            .unwrap()
+            .lines()
+            .map(|line| line.line_no())
+            .collect();
+        assert_eq!(numbers, vec![6, 7]);
+    }
+
+    #[test]
+    fn test_sections_crlf() {
+        let unix: Vec<Section> = sections("1-3\n5-8\n\n7\n").collect();
+        let windows: Vec<Section> = sections("1-3\r\n5-8 \r\n\r\n7\r\n\r\n").collect();
+        assert_eq!(windows, unix);
+        assert!(matches!(unix[0].text, Cow::Borrowed(_)));
+        assert!(matches!(windows[0].text, Cow::Owned(_)));
+    }
+}
@@ -0,0 +1,73 @@
+//! What a day does with an input line it can't parse.
+//!
+//! Strict, the default, fails the whole parse on the first bad line. Lenient
+//! skips the line instead and remembers why, and [`solve`](crate::solve)
+//! hands the skipped lines back in [`Timings::skipped`](crate::Timings) so
+//! the caller can list them. Only inputs made of one record per line can be
+//! lenient; grids and section headers are always strict.
+//!
+//! Both the policy and the skipped lines are kept per thread, so days parsed
+//! on different threads (or in parallel tests) don't see each other's.
+
+use std::cell::{Cell, RefCell};
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Policy {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+thread_local! {
+    static POLICY: Cell<Policy> = const { Cell::new(Policy::Strict) };
+    static SKIPPED: RefCell<Vec<Error>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Use `policy` for every parse on this thread from now on.
+pub fn set(policy: Policy) {
+    POLICY.with(|p| p.set(policy));
+}
+
+pub fn current() -> Policy {
+    POLICY.with(Cell::get)
+}
+
+/// Apply the policy to the result of parsing one line. A bad line is returned
+/// as an error when strict; when lenient it's recorded and skipped as `None`.
+pub fn line<T>(result: Result<T>) -> Result<Option<T>> {
+    match (result, current()) {
+        (Ok(value), _) => Ok(Some(value)),
+        (Err(e), Policy::Strict) => Err(e),
+        (Err(e), Policy::Lenient) => {
+            SKIPPED.with(|skipped| skipped.borrow_mut().push(e));
+            Ok(None)
+        }
+    }
+}
+
+/// The lines skipped on this thread since the last call.
+pub fn take_skipped() -> Vec<Error> {
+    SKIPPED.with(|skipped| skipped.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy() {
+        let bad = || Err::<i64, _>(Error::new("bad line"));
+
+        assert_eq!(line(Ok(3)), Ok(Some(3)));
+        assert_eq!(line(bad()), Err(Error::new("bad line")));
+        assert!(take_skipped().is_empty());
+
+        set(Policy::Lenient);
+        assert_eq!(line(bad()), Ok(None));
+        assert_eq!(take_skipped(), vec![Error::new("bad line")]);
+        assert!(take_skipped().is_empty());
+        set(Policy::Strict);
+    }
+}
@@ -0,0 +1,303 @@
+//! Drawing grids, in colour in the terminal or as PPM and PNG images.
+//!
+//! A [`Render`] is a colour map, which picks the colour of each cell from
+//! its value, and any number of highlighted cells, which are drawn in their
+//! own colour on top. The same one can draw any number of grids, such as the
+//! frames of an [`animate::Recorder`](crate::animate::Recorder). The terminal
+//! uses 24-bit ANSI colours and prints each cell with its `Display`; images
+//! draw each cell as a square of pixels.
+//!
+//! ```no_run
+//! use utils::geom::Point2;
+//! use utils::parse_char_grid;
+//! use utils::render::{Render, Rgb};
+//!
+//! let grid = parse_char_grid("#..\n.#.\n..#").unwrap();
+//! let render = Render::new()
+//!     .colours(|&c| (c == '#').then_some(Rgb::RED))
+//!     .highlight([Point2::new(1, 0)], Rgb::YELLOW)
+//!     .scale(8);
+//! render.print(&grid);
+//! render.save(&grid, "grid.png").unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{Display, Write as _};
+use std::io;
+use std::path::Path;
+
+use crate::geom::Point2;
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+}
+
+type ColourMap<'a, T> = Box<dyn Fn(&T) -> Option<Rgb> + 'a>;
+
+/// How to colour a grid
+pub struct Render<'a, T> {
+    colours: ColourMap<'a, T>,
+    highlights: HashMap<Point2<usize>, Rgb>,
+    background: Rgb,
+    scale: usize,
+}
+
+impl<'a, T> Render<'a, T> {
+    /// No colours at first: plain text in the terminal, and a black image.
+    pub fn new() -> Self {
+        Self {
+            colours: Box::new(|_| None),
+            highlights: HashMap::new(),
+            background: Rgb::BLACK,
+            scale: 1,
+        }
+    }
+
+    /// Colour each cell by its value. Cells it returns `None` for are left
+    /// plain in the terminal and drawn in the background colour in images.
+    pub fn colours(mut self, colours: impl Fn(&T) -> Option<Rgb> + 'a) -> Self {
+        self.colours = Box::new(colours);
+        self
+    }
+
+    /// Draw `points` in `colour`, whatever their value. Points off the grid
+    /// are ignored.
+    pub fn highlight(
+        mut self,
+        points: impl IntoIterator<Item = Point2<usize>>,
+        colour: Rgb,
+    ) -> Self {
+        self.highlights
+            .extend(points.into_iter().map(|p| (p, colour)));
+        self
+    }
+
+    /// Colour of uncoloured cells in images
+    pub fn background(mut self, colour: Rgb) -> Self {
+        self.background = colour;
+        self
+    }
+
+    /// Pixels per cell along each side in images
+    pub fn scale(mut self, scale: usize) -> Self {
+        assert!(scale > 0, "scale must be at least 1");
+        self.scale = scale;
+        self
+    }
+
+    /// Colour of the cell at `p` in an image
+    fn pixel(&self, grid: &Grid<T>, p: Point2<usize>) -> Rgb {
+        if let Some(&colour) = self.highlights.get(&p) {
+            return colour;
+        }
+        (self.colours)(&grid[p]).unwrap_or(self.background)
+    }
+
+    /// Every pixel of the grid's image, row by row, with its width and height
+    pub(crate) fn pixels(&self, grid: &Grid<T>) -> (usize, usize, Vec<Rgb>) {
+        let (width, height) = self.size(grid);
+        let pixels = (0..height)
+            .flat_map(|row| {
+                let y = row / self.scale;
+                (0..width).map(move |col| self.pixel(grid, Point2::new(col / self.scale, y)))
+            })
+            .collect();
+        (width, height, pixels)
+    }
+
+    /// Binary PPM (`P6`) image of the grid
+    pub fn ppm(&self, grid: &Grid<T>) -> Vec<u8> {
+        let (width, height, pixels) = self.pixels(grid);
+        let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+        out.extend(pixels.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        out
+    }
+
+    /// PNG image of the grid. The pixels aren't compressed, which keeps this
+    /// simple at the cost of bigger files.
+    pub fn png(&self, grid: &Grid<T>) -> Vec<u8> {
+        let (width, height, pixels) = self.pixels(grid);
+
+        let mut header = Vec::with_capacity(13);
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, default compression, filter and interlace
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Every scanline starts with its filter type, 0 for none
+        let mut scanlines = Vec::with_capacity(height * (width * 3 + 1));
+        for row in pixels.chunks(width.max(1)) {
+            scanlines.push(0);
+            scanlines.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        }
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut out, b"IHDR", &header);
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&scanlines));
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    /// Write the grid to `path` as a PNG or PPM image, going by its
+    /// extension.
+    pub fn save(&self, grid: &Grid<T>, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => self.png(grid),
+            Some("ppm") => self.ppm(grid),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} should end in .png or .ppm", path.display()),
+                ));
+            }
+        };
+        std::fs::write(path, bytes)
+    }
+
+    /// Image size in pixels
+    fn size(&self, grid: &Grid<T>) -> (usize, usize) {
+        (grid.width() * self.scale, grid.height() * self.scale)
+    }
+}
+
+impl<T> Default for Render<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Display> Render<'_, T> {
+    /// The grid as text, with ANSI escapes for the colours: cell colours in
+    /// the foreground, highlights in the background.
+    pub fn ansi(&self, grid: &Grid<T>) -> String {
+        let mut out = String::new();
+        for (y, row) in grid.iter_rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let highlight = self.highlights.get(&Point2::new(x, y));
+                let colour = (self.colours)(cell);
+                if let Some(Rgb(r, g, b)) = colour {
+                    let _ = write!(out, "\x1b[38;2;{r};{g};{b}m");
+                }
+                if let Some(Rgb(r, g, b)) = highlight {
+                    let _ = write!(out, "\x1b[48;2;{r};{g};{b}m");
+                }
+                let _ = write!(out, "{cell}");
+                if colour.is_some() || highlight.is_some() {
+                    out.push_str("\x1b[0m");
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Print the grid to the terminal in colour
+    pub fn print(&self, grid: &Grid<T>) {
+        print!("{}", self.ansi(grid));
+    }
+}
+
+/// Append a PNG chunk: length, type, data and the CRC of type and data
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// `data` as a zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const BLOCK: usize = u16::MAX as usize;
+
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(BLOCK).peekable();
+    if blocks.peek().is_none() {
+        // An empty stream still needs one final block
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_char_grid;
+
+    #[test]
+    fn test_ansi() {
+        let grid = parse_char_grid("#.\n.#").unwrap();
+        let render = Render::new()
+            .colours(|&c| (c == '#').then_some(Rgb(1, 2, 3)))
+            .highlight([Point2::new(1, 0)], Rgb(4, 5, 6));
+        assert_eq!(
+            render.ansi(&grid),
+            "\x1b[38;2;1;2;3m#\x1b[0m\x1b[48;2;4;5;6m.\x1b[0m\n.\x1b[38;2;1;2;3m#\x1b[0m\n"
+        );
+        assert_eq!(Render::new().ansi(&grid), "#.\n.#\n");
+    }
+
+    #[test]
+    fn test_images() {
+        let grid = parse_char_grid("#.").unwrap();
+        let render = Render::new()
+            .colours(|&c| (c == '#').then_some(Rgb::WHITE))
+            .scale(2);
+
+        let ppm = render.ppm(&grid);
+        assert!(ppm.starts_with(b"P6\n4 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 2 * 3);
+        assert_eq!(&ppm[11..17], &[255; 6]);
+        assert_eq!(&ppm[17..23], &[0; 6]);
+
+        let png = render.png(&grid);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+}
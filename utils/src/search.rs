@@ -0,0 +1,449 @@
+//! Breadth-first, Dijkstra and A* searches over any graph.
+//!
+//! A graph is just a function from a state to its neighbours: plain states
+//! for [`bfs`], `(state, cost)` pairs for [`dijkstra`] and [`astar`]. Every
+//! search takes several starting states, and remembers how it reached each
+//! state so [`Visited::path_to`] can walk back to a start.
+//!
+//! [`grid_4`], [`grid_8`] and [`grid_weighted_4`] turn a [`Grid`] into such a
+//! function, and [`adjacency`] does the same for a map of edge lists.
+//!
+//! ```
+//! use utils::geom::Point2;
+//! use utils::{parse_char_grid, search};
+//!
+//! let grid = parse_char_grid("..#\n#..\n...").unwrap();
+//! let start = Point2::new(0_usize, 0);
+//! let end = Point2::new(2, 2);
+//! let path = search::bfs_to([start], search::grid_4(&grid, |&c| c == '.'), |&p| p == end);
+//! assert_eq!(path.map(|p| p.cost), Some(4));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+use std::{array, iter, slice};
+
+use crate::geom::{Dir4, Dir8, Point2};
+use crate::grid::Grid;
+
+/// Edge weights and path lengths. `Default` is the cost of not moving.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// A route found by a search, from a start to `states.last()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+/// Every state a search reached, with its cost and the state it came from.
+#[derive(Debug, Clone)]
+pub struct Visited<S, C> {
+    seen: HashMap<S, (C, Option<S>)>,
+}
+
+impl<S: Eq + Hash + Clone, C: Copy> Visited<S, C> {
+    fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.seen.contains_key(state)
+    }
+
+    /// Cost of the cheapest way found to `state`
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.seen.get(state).map(|&(cost, _)| cost)
+    }
+
+    pub fn states(&self) -> impl Iterator<Item = &S> {
+        self.seen.keys()
+    }
+
+    /// The route to `state`, from whichever start it was reached from
+    pub fn path_to(&self, state: &S) -> Option<Path<S, C>> {
+        let cost = self.cost(state)?;
+        let mut states = vec![state.clone()];
+        while let Some((_, Some(prev))) = self.seen.get(states.last().unwrap()) {
+            states.push(prev.clone());
+        }
+        states.reverse();
+        Some(Path { cost, states })
+    }
+}
+
+/// Breadth-first search from `starts` until nothing new can be reached.
+/// The cost of a state is its number of steps from the nearest start.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+) -> Visited<S, usize>
+where
+    S: Eq + Hash + Clone,
+    I: IntoIterator<Item = S>,
+{
+    breadth_first(starts, neighbors, |_| false).0
+}
+
+/// Breadth-first search from `starts` that stops at the first state `goal`
+/// accepts, which is one of the closest.
+pub fn bfs_to<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Eq + Hash + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let (visited, found) = breadth_first(starts, neighbors, goal);
+    visited.path_to(&found?)
+}
+
+fn breadth_first<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> (Visited<S, usize>, Option<S>)
+where
+    S: Eq + Hash + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if goal(&start) {
+            visited.seen.insert(start.clone(), (0, None));
+            return (visited, Some(start));
+        }
+        if let Entry::Vacant(entry) = visited.seen.entry(start.clone()) {
+            entry.insert((0, None));
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in neighbors(&state) {
+            let Entry::Vacant(entry) = visited.seen.entry(next.clone()) else {
+                continue;
+            };
+            entry.insert((steps + 1, Some(state.clone())));
+            if goal(&next) {
+                return (visited, Some(next));
+            }
+            queue.push_back((next, steps + 1));
+        }
+    }
+
+    (visited, None)
+}
+
+/// Cheapest cost from `starts` to every state that can be reached.
+/// Costs must not be negative.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+) -> Visited<S, C>
+where
+    S: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    best_first(starts, neighbors, |_| C::default(), |_| false).0
+}
+
+/// Cheapest route from `starts` to any state `goal` accepts.
+pub fn dijkstra_to<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (visited, found) = best_first(starts, neighbors, |_| C::default(), goal);
+    visited.path_to(&found?)
+}
+
+/// Like [`dijkstra_to`], but `heuristic` estimates the cost left from a
+/// state to the goal so the search can head towards it. The estimate must
+/// never be more than the real cost, or the route found may not be the
+/// cheapest.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let (visited, found) = best_first(starts, neighbors, heuristic, goal);
+    visited.path_to(&found?)
+}
+
+/// A state waiting in the heap, ordered so the lowest priority pops first
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+fn best_first<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> (Visited<S, C>, Option<S>)
+where
+    S: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut visited = Visited::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = visited.seen.entry(start.clone()) {
+            entry.insert((C::default(), None));
+            heap.push(Queued {
+                priority: heuristic(&start),
+                cost: C::default(),
+                state: start,
+            });
+        }
+    }
+
+    while let Some(Queued { cost, state, .. }) = heap.pop() {
+        // A cheaper way here was already handled
+        if visited.cost(&state).is_some_and(|best| best < cost) {
+            continue;
+        }
+        if goal(&state) {
+            return (visited, Some(state));
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if visited.cost(&next).is_some_and(|best| best <= next_cost) {
+                continue;
+            }
+            visited
+                .seen
+                .insert(next.clone(), (next_cost, Some(state.clone())));
+            heap.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    (visited, None)
+}
+
+/// Number of distinct routes from `start` to states `goal` accepts, which
+/// don't continue past a goal. The graph must have no cycles.
+pub fn count_paths<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> u64
+where
+    S: Eq + Hash + Clone,
+    I: IntoIterator<Item = S>,
+{
+    fn count<S: Eq + Hash + Clone, I: IntoIterator<Item = S>>(
+        state: &S,
+        neighbors: &mut impl FnMut(&S) -> I,
+        goal: &mut impl FnMut(&S) -> bool,
+        memo: &mut HashMap<S, u64>,
+    ) -> u64 {
+        if goal(state) {
+            return 1;
+        }
+        if let Some(&known) = memo.get(state) {
+            return known;
+        }
+        let mut total = 0;
+        for next in neighbors(state) {
+            total += count(&next, neighbors, goal, memo);
+        }
+        memo.insert(state.clone(), total);
+        total
+    }
+
+    count(&start, &mut neighbors, &mut goal, &mut HashMap::new())
+}
+
+/// Up to `N` neighbours, without allocating
+pub type Moves<T, const N: usize> = iter::Flatten<array::IntoIter<Option<T>, N>>;
+
+/// The point one step from `p` in the direction of `offset`, if that isn't
+/// off the top or left of the grid
+fn step(p: Point2<usize>, offset: Point2<i8>) -> Option<Point2<usize>> {
+    Some(Point2::new(
+        p.x.checked_add_signed(offset.x.into())?,
+        p.y.checked_add_signed(offset.y.into())?,
+    ))
+}
+
+/// Steps up, right, down and left onto cells that `open` accepts.
+pub fn grid_4<T>(
+    grid: &Grid<T>,
+    open: impl Fn(&T) -> bool,
+) -> impl Fn(&Point2<usize>) -> Moves<Point2<usize>, 4> {
+    move |&p| {
+        Dir4::ALL
+            .map(|dir| step(p, dir.offset()).filter(|&q| grid.at(q).is_some_and(&open)))
+            .into_iter()
+            .flatten()
+    }
+}
+
+/// Steps in all eight directions onto cells that `open` accepts.
+pub fn grid_8<T>(
+    grid: &Grid<T>,
+    open: impl Fn(&T) -> bool,
+) -> impl Fn(&Point2<usize>) -> Moves<Point2<usize>, 8> {
+    move |&p| {
+        Dir8::ALL
+            .map(|dir| step(p, dir.offset()).filter(|&q| grid.at(q).is_some_and(&open)))
+            .into_iter()
+            .flatten()
+    }
+}
+
+/// Steps up, right, down and left, costing whatever `cost` says it takes to
+/// enter the cell. Cells it returns `None` for can't be entered.
+pub fn grid_weighted_4<T, C>(
+    grid: &Grid<T>,
+    cost: impl Fn(&T) -> Option<C>,
+) -> impl Fn(&Point2<usize>) -> Moves<(Point2<usize>, C), 4> {
+    move |&p| {
+        Dir4::ALL
+            .map(|dir| {
+                let q = step(p, dir.offset())?;
+                Some((q, cost(grid.at(q)?)?))
+            })
+            .into_iter()
+            .flatten()
+    }
+}
+
+/// The edges of a graph given as each state's list of neighbours. States
+/// with no list have no edges.
+pub fn adjacency<'a, S: Eq + Hash + Clone>(
+    edges: &'a HashMap<S, Vec<S>>,
+) -> impl Fn(&S) -> iter::Cloned<slice::Iter<'a, S>> + 'a {
+    move |state: &S| {
+        edges
+            .get(state)
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_char_grid;
+
+    #[test]
+    fn test_bfs() {
+        let grid = parse_char_grid("...\n.#.\n#..").unwrap();
+        let open = grid_4(&grid, |&c| c == '.');
+
+        let visited = bfs([Point2::new(0, 0)], &open);
+        assert_eq!(visited.len(), 7);
+        assert_eq!(visited.cost(&Point2::new(2, 2)), Some(4));
+        assert!(!visited.contains(&Point2::new(1, 1)));
+
+        let path = bfs_to([Point2::new(0, 2), Point2::new(0, 0)], &open, |&p| {
+            p == Point2::new(2, 0)
+        })
+        .unwrap();
+        assert_eq!(path.cost, 2);
+        assert_eq!(
+            path.states,
+            [Point2::new(0, 0), Point2::new(1, 0), Point2::new(2, 0)]
+        );
+
+        let diagonal = bfs_to([Point2::new(0, 0)], grid_8(&grid, |&c| c == '.'), |&p| {
+            p == Point2::new(2, 2)
+        });
+        assert_eq!(diagonal.map(|p| p.cost), Some(3));
+    }
+
+    #[test]
+    fn test_weighted() {
+        let grid = parse_char_grid("19\n11").unwrap();
+        let weights = grid_weighted_4(&grid, |c| c.to_digit(10));
+        let end = Point2::new(1, 1);
+
+        let path = dijkstra_to([Point2::new(0, 0)], &weights, |&p| p == end).unwrap();
+        assert_eq!(path.cost, 2);
+        assert_eq!(path.states, [Point2::new(0, 0), Point2::new(0, 1), end]);
+
+        let manhattan = |p: &Point2<usize>| (end.x.abs_diff(p.x) + end.y.abs_diff(p.y)) as u32;
+        let guided = astar([Point2::new(0, 0)], &weights, manhattan, |&p| p == end);
+        assert_eq!(guided, Some(path));
+
+        let all = dijkstra([Point2::new(0, 0)], &weights);
+        assert_eq!(all.cost(&Point2::new(1, 0)), Some(9));
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let edges: HashMap<&str, Vec<&str>> = [
+            ("a", vec!["b", "c"]),
+            ("b", vec!["c", "d"]),
+            ("c", vec!["d"]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(count_paths("a", adjacency(&edges), |&s| s == "d"), 3);
+        assert_eq!(count_paths("d", adjacency(&edges), |&s| s == "a"), 0);
+    }
+}
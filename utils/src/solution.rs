@@ -0,0 +1,342 @@
+use std::fmt;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::alloc::{self, AllocStats};
+use crate::animate::{self, Output, Visualize};
+use crate::error::Error;
+use crate::input::{self, INPUT_DIR_VAR};
+use crate::policy::{self, Policy};
+
+/// A puzzle answer. Most days produce a number, a few produce text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => fmt::Display::fmt(n, f),
+            Answer::Text(s) => f.pad(s),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        // Anything too big for i64 is still a valid answer, just not a numeric one
+        i64::try_from(n)
+            .map(Answer::Int)
+            .unwrap_or_else(|_| Answer::Text(n.to_string()))
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+/// Implemented once per day so the runner can call the day in-process
+/// instead of spawning its binary and scraping stdout.
+///
+/// Parsing is split out from the parts so every day is timed the same way:
+/// parse once, then each part works from the parsed input. Malformed input
+/// is reported by `parse`, so the parts can assume it's well formed.
+pub trait Solution {
+    /// Day number, used to find the input file and label results
+    const DAY: u8;
+
+    /// The parsed puzzle input, which may borrow from the raw text
+    type Input<'a>;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error>;
+    fn part1(input: &Self::Input<'_>) -> Self::Part1;
+    fn part2(input: &Self::Input<'_>) -> Self::Part2;
+}
+
+/// Which parts of a day to run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Parts {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    pub fn includes(self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Parts::Both, _) | (Parts::One, 1) | (Parts::Two, 2)
+        )
+    }
+}
+
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Parts::One),
+            "2" => Ok(Parts::Two),
+            "both" => Ok(Parts::Both),
+            _ => Err(format!("invalid part '{s}', expected 1, 2 or both")),
+        }
+    }
+}
+
+/// Answer and cost of solving one part.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub answer: Answer,
+    pub time: Duration,
+    /// Only measured when allocation counting is on, see [`alloc`]
+    pub alloc: Option<AllocStats>,
+}
+
+/// Answers and timings from one run of a day. Parts that weren't asked for
+/// are `None`.
+#[derive(Debug, Clone)]
+pub struct Timings {
+    pub parse: Duration,
+    pub parse_alloc: Option<AllocStats>,
+    pub part1: Option<PartResult>,
+    pub part2: Option<PartResult>,
+    /// Lines the parse skipped, only ever non-empty under [`Policy::Lenient`]
+    pub skipped: Vec<Error>,
+}
+
+impl Timings {
+    pub fn part(&self, part: u8) -> Option<&PartResult> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse
+            + [&self.part1, &self.part2]
+                .into_iter()
+                .flatten()
+                .map(|part| part.time)
+                .sum::<Duration>()
+    }
+}
+
+/// Parse the input and solve the requested parts, timing each step.
+///
+/// This is the only place timing boundaries are defined, so the day
+/// binaries and the runner always measure the same thing.
+pub fn solve<S: Solution>(input: &str, parts: Parts) -> Result<Timings, Error> {
+    // Drop anything left over from a parse that failed part way
+    policy::take_skipped();
+    let ((parsed, parse), parse_alloc) = alloc::measure(|| {
+        let start = Instant::now();
+        let parsed = S::parse(input);
+        (parsed, start.elapsed())
+    });
+    let parsed = parsed?;
+    let skipped = policy::take_skipped();
+
+    let part = |solve: &dyn Fn() -> Answer| {
+        let ((answer, time), alloc) = alloc::measure(|| {
+            let start = Instant::now();
+            let answer = solve();
+            (answer, start.elapsed())
+        });
+        PartResult {
+            answer,
+            time,
+            alloc,
+        }
+    };
+
+    Ok(Timings {
+        parse,
+        parse_alloc,
+        part1: parts
+            .includes(1)
+            .then(|| part(&|| S::part1(&parsed).into())),
+        part2: parts
+            .includes(2)
+            .then(|| part(&|| S::part2(&parsed).into())),
+        skipped,
+    })
+}
+
+fn usage(day: u8) -> String {
+    format!(
+        "Usage: day{day} [--part 1|2|both] [--strict | --lenient]
+            [--visualize[=terminal|gif|ppm] [--fps <N>]] [INPUT]
+
+  INPUT        Puzzle input file, or '-' to read stdin. Relative paths are
+               tried from the workspace root, then the current directory.
+               Defaults to ${INPUT_DIR_VAR}/day{day}.txt, or inputs/day{day}.txt
+               in the workspace root.
+  --part <P>   Only run part 1, part 2 or both (default both)
+  --strict     Fail on the first input line that can't be parsed (default)
+  --lenient    Skip input lines that can't be parsed and list them afterwards
+  --visualize  Watch the days that can show their work: replay it in the
+               terminal (default), or write it to a GIF or numbered PPM files
+               in the current directory
+  --fps <N>    Frames per second for --visualize (default 10)
+  -h, --help   Print this help"
+    )
+}
+
+/// Command line shared by every day binary.
+#[derive(Debug, Default)]
+struct Args {
+    input: Option<String>,
+    parts: Parts,
+    policy: Option<Policy>,
+    visualize: Option<Output>,
+    fps: Option<u32>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--part" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                parsed.parts = value.parse()?;
+            }
+            "--strict" | "--lenient" => {
+                let policy = match arg.as_str() {
+                    "--strict" => Policy::Strict,
+                    _ => Policy::Lenient,
+                };
+                if parsed.policy.is_some_and(|p| p != policy) {
+                    return Err("--strict and --lenient can't be used together".to_string());
+                }
+                parsed.policy = Some(policy);
+            }
+            flag if flag.starts_with("--part=") => {
+                parsed.parts = flag["--part=".len()..].parse()?
+            }
+            "--visualize" => parsed.visualize = Some(Output::Terminal),
+            flag if flag.starts_with("--visualize=") => {
+                parsed.visualize = Some(flag["--visualize=".len()..].parse()?)
+            }
+            "--fps" => {
+                let value = args.next().ok_or("--fps needs a value")?;
+                parsed.fps = Some(parse_fps(&value)?);
+            }
+            flag if flag.starts_with("--fps=") => {
+                parsed.fps = Some(parse_fps(&flag["--fps=".len()..])?)
+            }
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(format!("unknown argument '{flag}'"));
+            }
+            path if parsed.input.is_none() => parsed.input = Some(path.to_string()),
+            extra => return Err(format!("unexpected argument '{extra}'")),
+        }
+    }
+
+    if parsed.fps.is_some() && parsed.visualize.is_none() {
+        return Err("--fps only applies with --visualize".to_string());
+    }
+
+    Ok(Some(parsed))
+}
+
+fn parse_fps(value: &str) -> Result<u32, String> {
+    match value.parse() {
+        Ok(fps) if fps > 0 => Ok(fps),
+        _ => Err(format!(
+            "invalid --fps value '{value}', expected a positive number"
+        )),
+    }
+}
+
+/// Shared `main` for the day binaries: read the input named on the command
+/// line (see [`input::resolve`]), solve the requested parts and print the
+/// answers along with how long parsing and each part took.
+pub fn run<S: Solution>() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", usage(S::DAY));
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", usage(S::DAY));
+            return ExitCode::from(2);
+        }
+    };
+
+    policy::set(args.policy.unwrap_or_default());
+    animate::set(args.visualize.map(|output| Visualize {
+        output,
+        fps: args.fps.unwrap_or(Visualize::default().fps),
+    }));
+
+    let source = input::resolve(S::DAY, args.input.as_deref());
+    println!("Loading input from {source}");
+
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: failed to read input: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let timings = match solve::<S>(&input, args.parts) {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("{}", e.render(&input));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for part in [1, 2] {
+        if let Some(result) = timings.part(part) {
+            println!("Part {part}: {}", result.answer);
+        }
+    }
+    println!("Parse time: {:?}", timings.parse);
+    for part in [1, 2] {
+        if let Some(result) = timings.part(part) {
+            println!("Part {part} time: {:?}", result.time);
+        }
+    }
+    println!("Execution time: {:?}", timings.total());
+    animate::flush();
+
+    if !timings.skipped.is_empty() {
+        eprintln!(
+            "warning: skipped {} input line(s) that couldn't be parsed",
+            timings.skipped.len()
+        );
+        for e in &timings.skipped {
+            eprintln!("  {e}");
+        }
+    }
+
+    ExitCode::SUCCESS
+}
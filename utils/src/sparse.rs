@@ -0,0 +1,232 @@
+//! Grids with no fixed size, for worlds that are mostly empty or whose
+//! coordinates are too big, or too negative, for a [`Grid`].
+//!
+//! Only the cells that have been set are stored. Coordinates are signed
+//! [`Point2`]s with `x` as the column and `y` as the row, the same as on a
+//! [`Grid`], and [`SparseGrid::to_dense`] copies the occupied rectangle into
+//! one.
+
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::{Dir4, Dir8, Point2};
+use crate::grid::Grid;
+
+pub type Point = Point2<i64>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Number of cells that are set
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn at(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn at_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    /// Set the cell at `p`, returning what was there before
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    /// Clear the cell at `p`, returning what was there
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    /// The cell at `p`, set to `default()` first if it's empty
+    pub fn get_or_insert_with(&mut self, p: Point, default: impl FnOnce() -> T) -> &mut T {
+        self.cells.entry(p).or_insert_with(default)
+    }
+
+    /// Positions of the cells that are set, in no particular order
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Every cell that's set with its position, in no particular order
+    pub fn iter_cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, cell)| (p, cell))
+    }
+
+    /// Top left and bottom right corners of the smallest rectangle holding
+    /// every cell that's set, or `None` if there are none. The bounds aren't
+    /// stored, so this looks at every cell each time.
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        let mut points = self.points();
+        let first = points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point2::new(min.x.min(p.x), min.y.min(p.y)),
+                Point2::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
+    /// Columns spanned by the bounding box, which takes a pass over every
+    /// cell like [`bounding_box`](Self::bounding_box)
+    pub fn width(&self) -> usize {
+        self.bounding_box()
+            .map_or(0, |(min, max)| (max.x - min.x) as usize + 1)
+    }
+
+    /// Rows spanned by the bounding box, which takes a pass over every cell
+    /// like [`bounding_box`](Self::bounding_box)
+    pub fn height(&self) -> usize {
+        self.bounding_box()
+            .map_or(0, |(min, max)| (max.y - min.y) as usize + 1)
+    }
+
+    /// Neighbours of `p` up, right, down and left that are set, with their
+    /// values. The order matches [`Grid::neighbors_4_at`], so a search visits
+    /// cells in the same order on either grid.
+    pub fn neighbors_4_at(&self, p: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.occupied(p + dir))
+    }
+
+    /// Neighbours of `p` that are set, including diagonals, with their values,
+    /// clockwise from up like [`Grid::neighbors_8_at`]
+    pub fn neighbors_8_at(&self, p: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.occupied(p + dir))
+    }
+
+    fn occupied(&self, p: Point) -> Option<(Point, &T)> {
+        self.cells.get(&p).map(|cell| (p, cell))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Copy the bounding box into a [`Grid`], with `empty` in the cells that
+    /// aren't set. The grid's `(0, 0)` is the top left of
+    /// [`bounding_box`](Self::bounding_box).
+    pub fn to_dense(&self, empty: T) -> Grid<T> {
+        let Some((min, max)) = self.bounding_box() else {
+            return Grid::from_vec(0, 0, Vec::new());
+        };
+        let width = (max.x - min.x) as usize + 1;
+        let height = (max.y - min.y) as usize + 1;
+        Grid::from_fn(height, width, |row, col| {
+            let p = min + Point2::new(col as i64, row as i64);
+            self.cells.get(&p).unwrap_or(&empty).clone()
+        })
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        self.cells.extend(iter);
+    }
+}
+
+/// `grid[p]` for a cell that's set. Panics if it's empty.
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.at(p).expect("no cell at that point")
+    }
+}
+
+impl<T> IndexMut<Point> for SparseGrid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.at_mut(p).expect("no cell at that point")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_char_grid;
+
+    #[test]
+    fn test_sparse() {
+        let mut grid: SparseGrid<char> = [(Point2::new(-2, 5), 'a'), (Point2::new(1, 3), 'b')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.insert(Point2::new(1, 3), 'c'), Some('b'));
+        grid.insert(Point2::new(1_000_000_000_000, -7), 'z');
+        grid.remove(Point2::new(1_000_000_000_000, -7));
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid[Point2::new(-2, 5)], 'a');
+        assert_eq!(grid.at(Point2::new(0, 0)), None);
+        assert_eq!(
+            grid.bounding_box(),
+            Some((Point2::new(-2, 3), Point2::new(1, 5)))
+        );
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+
+        grid.insert(Point2::new(-1, 4), 'd');
+        let around: Vec<_> = grid.neighbors_4_at(Point2::new(-2, 4)).collect();
+        assert_eq!(
+            around,
+            vec![(Point2::new(-1, 4), &'d'), (Point2::new(-2, 5), &'a')]
+        );
+        assert_eq!(grid.neighbors_8_at(Point2::new(0, 4)).count(), 2);
+
+        let dense = grid.to_dense('.');
+        assert_eq!(dense, parse_char_grid("...c\n.d..\na...").unwrap());
+        assert_eq!(SparseGrid::<char>::new().to_dense('.').width(), 0);
+    }
+
+    #[test]
+    fn test_neighbour_order() {
+        let dense = parse_char_grid("abc\ndef\nghi").unwrap();
+        let sparse: SparseGrid<char> = dense
+            .points()
+            .map(|p| (Point2::new(p.x as i64, p.y as i64), dense[p]))
+            .collect();
+        let centre = Point2::new(1, 1);
+
+        let dense_4: Vec<_> = dense.neighbors_4_at(centre).map(|(_, &c)| c).collect();
+        let sparse_4: Vec<_> = sparse.neighbors_4_at(centre).map(|(_, &c)| c).collect();
+        assert_eq!(dense_4, vec!['b', 'f', 'h', 'd']);
+        assert_eq!(sparse_4, dense_4);
+
+        let dense_8: Vec<_> = dense.neighbors_8_at(centre).map(|(_, &c)| c).collect();
+        let sparse_8: Vec<_> = sparse.neighbors_8_at(centre).map(|(_, &c)| c).collect();
+        assert_eq!(dense_8, vec!['b', 'c', 'f', 'i', 'h', 'g', 'd', 'a']);
+        assert_eq!(sparse_8, dense_8);
+    }
+}